@@ -0,0 +1,308 @@
+use std::{
+    fs::{self, File},
+    io::{self, BufWriter, Read, Write},
+    path::{Path, PathBuf},
+};
+
+use anyhow::Context;
+use clap::{Parser, Subcommand};
+use iced_x86::{Decoder, DecoderOptions};
+use pe_parse::{
+    ImageDosHeader, ImageFileHeader, OptionalHeaders, OptionalHeaders32, OptionalHeaders64,
+};
+use serde_json::Value;
+
+use crate::{
+    components::headertable::HeaderData,
+    pe::{self, address::AddressSpace, flags, report, sections},
+    routes::{
+        assembly::assembly::CustomFormatter,
+        hexdump::hexdump::{ROW_LEN, row_ascii, row_hex},
+        info::info::{decode_fields, parse_data, parse_data_directory_from_array},
+    },
+};
+
+/// Bytes of the file read at a time by `hexdump`, a multiple of the row length
+const HEXDUMP_CHUNK: u64 = 0x10000;
+
+/// Without a subcommand the GUI is started, optionally with `file` already open
+#[derive(Parser)]
+#[command(
+    name = "rustdump",
+    version,
+    about = "PE file viewer",
+    args_conflicts_with_subcommands = true
+)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+    /// File to open in the GUI
+    pub file: Option<PathBuf>,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Print the DOS, file and optional headers, data directories, imports and exports
+    Info { file: PathBuf },
+    /// Print a hex dump of the whole file
+    Hexdump { file: PathBuf },
+    /// Disassemble the code of the file
+    Disasm { file: PathBuf },
+    /// Print the section table
+    Sections { file: PathBuf },
+    /// Write a JSON report of the headers, sections, imports, exports and hashes
+    Report {
+        file: PathBuf,
+        /// Write to this file instead of stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+}
+
+pub fn run(command: Command) -> anyhow::Result<()> {
+    let result = match command {
+        Command::Info { file } => info(&file),
+        Command::Hexdump { file } => hexdump(&file),
+        Command::Disasm { file } => disasm(&file),
+        Command::Sections { file } => sections(&file),
+        Command::Report { file, output } => {
+            let json = report_json(&file)?;
+            match output {
+                Some(output) => fs::write(&output, json)
+                    .with_context(|| format!("failed to write {}", output.display())),
+                None => {
+                    println!("{}", json);
+                    Ok(())
+                }
+            }
+        }
+    };
+    // The reader went away, e.g. `rustdump hexdump big.exe | head`
+    match result {
+        Err(err) if is_broken_pipe(&err) => Ok(()),
+        result => result,
+    }
+}
+
+fn is_broken_pipe(err: &anyhow::Error) -> bool {
+    err.chain().any(|cause| {
+        cause
+            .downcast_ref::<io::Error>()
+            .is_some_and(|err| err.kind() == io::ErrorKind::BrokenPipe)
+    })
+}
+
+/// Pretty printed JSON report of the file at `path`
+pub fn report_json(path: &Path) -> anyhow::Result<String> {
+    let (bytes, pe_header) = pe::read_pe(path)?;
+    let report = report::build_report(
+        &path.display().to_string(),
+        &bytes,
+        &pe_header,
+        pe::imports::parse_imports(&bytes, &pe_header),
+        pe::exports::parse_exports(&bytes, &pe_header),
+    );
+    Ok(serde_json::to_string_pretty(&report)?)
+}
+
+fn value_string(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Number(n) => match n.as_u64() {
+            Some(n) => format!("0x{:X}", n),
+            None => n.to_string(),
+        },
+        other => other.to_string(),
+    }
+}
+
+fn print_table(title: &str, rows: &[HeaderData]) {
+    println!("{}", title);
+    for row in rows {
+        println!(
+            "  {:<10}  {:<28} {:<24} {}",
+            row.offset,
+            row.name,
+            value_string(&row.value),
+            row.meaning
+        );
+    }
+    println!();
+}
+
+fn offset_string(offset: Option<usize>, base: usize) -> String {
+    format!("0x{:08X}", offset.map(|o| o + base).unwrap_or(0))
+}
+
+fn info(path: &Path) -> anyhow::Result<()> {
+    let (bytes, pe_header) = pe::read_pe(path)?;
+    let space = AddressSpace::new(&pe_header);
+    let nt_headers = space.nt_headers_offset();
+    let optional_header = space.optional_header_offset();
+
+    let dos_header = parse_data(
+        serde_json::to_value(&pe_header.dos_header)?,
+        |idx| offset_string(ImageDosHeader::get_offset(idx).map(usize::from), 0),
+        pe_parse::DOS_HEADER_MEANINGS.to_vec(),
+    );
+    print_table("DOS Header", &dos_header);
+
+    let file_header = parse_data(
+        serde_json::to_value(&pe_header.nt_header.image_file_header)?,
+        |idx| {
+            offset_string(
+                ImageFileHeader::get_offset(idx).map(usize::from),
+                nt_headers,
+            )
+        },
+        pe_parse::FILE_HEADER_MEANINGS.to_vec(),
+    );
+    print_table("File Header", &decode_fields(file_header));
+
+    let (opt_header, data_directories) = match &pe_header.nt_header.optional_headers {
+        OptionalHeaders::OptionalHeaders32(opt32) => (
+            parse_data(
+                serde_json::to_value(opt32)?,
+                |idx| {
+                    offset_string(
+                        OptionalHeaders32::get_offset(idx).map(usize::from),
+                        optional_header,
+                    )
+                },
+                pe_parse::OPTIONAL_HEADER32_MEANINGS.to_vec(),
+            ),
+            parse_data_directory_from_array(&opt32.data_directory, |idx| idx.to_string()),
+        ),
+        OptionalHeaders::OptionalHeaders64(opt64) => (
+            parse_data(
+                serde_json::to_value(opt64)?,
+                |idx| {
+                    offset_string(
+                        OptionalHeaders64::get_offset(idx).map(usize::from),
+                        optional_header,
+                    )
+                },
+                pe_parse::OPTIONAL_HEADER64_MEANINGS.to_vec(),
+            ),
+            parse_data_directory_from_array(&opt64.data_directory, |idx| idx.to_string()),
+        ),
+    };
+    print_table("Optional Header", &decode_fields(opt_header));
+    print_table("Data Directories", &data_directories);
+
+    let imports = pe::imports::parse_imports(&bytes, &pe_header);
+    println!("Imports ({} DLLs)", imports.len());
+    for dll in &imports {
+        println!("  {} ({} functions)", dll.name, dll.functions.len());
+        for function in &dll.functions {
+            match (&function.name, function.ordinal) {
+                (Some(name), _) => println!("    {}", name),
+                (None, Some(ordinal)) => println!("    #{}", ordinal),
+                (None, None) => println!("    0x{:X}", function.thunk),
+            }
+        }
+    }
+    println!();
+
+    match pe::exports::parse_exports(&bytes, &pe_header) {
+        Some(exports) => {
+            println!(
+                "Exports of {} ({} functions)",
+                exports.name,
+                exports.exports.len()
+            );
+            for export in &exports.exports {
+                let target = match &export.forwarder {
+                    Some(forwarder) => format!("-> {}", forwarder),
+                    None => format!("0x{:08X}", export.rva),
+                };
+                println!(
+                    "  {:>5}  {:<10}  {}",
+                    export.ordinal,
+                    target,
+                    export.name.as_deref().unwrap_or("")
+                );
+            }
+        }
+        None => println!("No exports"),
+    }
+
+    Ok(())
+}
+
+/// Streams the file in chunks, so memory use does not grow with the file size
+fn hexdump(path: &Path) -> anyhow::Result<()> {
+    let mut file =
+        File::open(path).with_context(|| format!("failed to read {}", path.display()))?;
+    let mut out = BufWriter::new(io::stdout().lock());
+    let mut chunk = Vec::with_capacity(HEXDUMP_CHUNK as usize);
+    let mut offset = 0;
+    loop {
+        chunk.clear();
+        let len = (&mut file)
+            .take(HEXDUMP_CHUNK)
+            .read_to_end(&mut chunk)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        if len == 0 {
+            break;
+        }
+        for row in chunk.chunks(ROW_LEN) {
+            writeln!(
+                out,
+                "{:08X}  {:<width$}  {}",
+                offset,
+                row_hex(row),
+                row_ascii(row),
+                width = ROW_LEN * 3 - 1
+            )?;
+            offset += row.len();
+        }
+    }
+    out.flush()?;
+    Ok(())
+}
+
+fn disasm(path: &Path) -> anyhow::Result<()> {
+    let (bytes, pe_header) = pe::read_pe(path)?;
+    let bitness = if pe::is_pe64(&pe_header) { 64 } else { 32 };
+    let mut formatter = CustomFormatter::new();
+    let mut output = String::new();
+    let mut out = BufWriter::new(io::stdout().lock());
+    for (va, code) in sections::code_sections(&bytes, &pe_header) {
+        let mut decoder = Decoder::with_ip(bitness, code, va, DecoderOptions::NONE);
+        for instr in decoder.iter() {
+            output.clear();
+            formatter.format(&instr, &mut output);
+            writeln!(out, "{:016X}  {}", instr.ip(), output)?;
+        }
+    }
+    out.flush()?;
+    Ok(())
+}
+
+fn sections(path: &Path) -> anyhow::Result<()> {
+    let (bytes, pe_header) = pe::read_pe(path)?;
+    let space = AddressSpace::new(&pe_header);
+
+    println!(
+        "{:<8}  {:<16}  {:<10}  {:<10}  {:<10}  {:<10}  {:<7}  Flags",
+        "Name", "VA", "VSize", "RawPtr", "RawSize", "Chars", "Entropy"
+    );
+    for sct in &pe_header.section_headers {
+        let name = String::from_utf8_lossy(&sct.name)
+            .trim_end_matches('\0')
+            .to_string();
+        println!(
+            "{:<8}  0x{:<14X}  0x{:08X}  0x{:08X}  0x{:08X}  0x{:08X}  {:<7.4}  {}",
+            name,
+            space.rva_to_va(sct.virtual_address),
+            sct.virtual_size,
+            sct.pointer_to_raw_data,
+            sct.size_of_raw_data,
+            sct.characteristics,
+            sections::entropy(sections::raw_data(&bytes, sct)),
+            flags::section_flag_names(sct.characteristics)
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,182 @@
+use std::cmp::Ordering;
+
+use gpui::{App, AppContext, Context, Entity, IntoElement, Window, point, px};
+use gpui_component::{
+    scroll::ScrollbarHandle,
+    table::{Column, ColumnSort, Table, TableDelegate, TableEvent, TableState},
+};
+
+use crate::RustDump;
+
+/// Vertical scroll position of a table, saved with the session
+pub fn table_scroll_offset<D: TableDelegate>(state: &Entity<TableState<D>>, cx: &App) -> f32 {
+    f32::from(state.read(cx).vertical_scroll_handle.offset().y)
+}
+
+pub fn set_table_scroll_offset<D: TableDelegate>(
+    state: &Entity<TableState<D>>,
+    offset: f32,
+    cx: &mut App,
+) {
+    state.update(cx, |state, cx| {
+        state
+            .vertical_scroll_handle
+            .set_offset(point(px(0.), px(offset)));
+        cx.notify();
+    });
+}
+
+/// A table the Info route can save and restore the scroll position of
+pub trait ScrollTable {
+    fn scroll_offset(&self, cx: &App) -> f32;
+    fn set_scroll_offset(&self, offset: f32, cx: &mut App);
+}
+
+/// Clones share the same table state
+#[derive(Clone)]
+pub struct DataTable {
+    pub table_state: Entity<TableState<DataTableDelegate>>,
+    cols: Vec<Column>,
+}
+
+impl DataTable {
+    pub fn new(cols: Vec<Column>, window: &mut Window, cx: &mut Context<RustDump>) -> Self {
+        let delegate = DataTableDelegate::new(cols.clone(), vec![]);
+        let table_state = cx.new(|cx| TableState::new(delegate, window, cx));
+        Self { table_state, cols }
+    }
+
+    pub fn load(
+        &mut self,
+        rows: Vec<Vec<String>>,
+        window: &mut Window,
+        cx: &mut Context<RustDump>,
+    ) {
+        let delegate = DataTableDelegate::new(self.cols.clone(), rows);
+        self.table_state = cx.new(|cx| TableState::new(delegate, window, cx));
+    }
+
+    /// Replaces the rows without creating a new table state, so it works without a window
+    /// and keeps `on_row_select` subscriptions
+    pub fn set_rows(&self, rows: Vec<Vec<String>>, cx: &mut App) {
+        let delegate = DataTableDelegate::new(self.cols.clone(), rows);
+        self.table_state.update(cx, |state, cx| {
+            *state.delegate_mut() = delegate;
+            state.refresh(cx);
+        });
+    }
+
+    /// Calls `on_select` with the unsorted row index whenever a row gets selected.
+    /// Must be called again after `load` since loading replaces the table state.
+    pub fn on_row_select(
+        &self,
+        cx: &mut Context<RustDump>,
+        on_select: impl Fn(&mut RustDump, usize, &mut Context<RustDump>) + 'static,
+    ) {
+        cx.subscribe(
+            &self.table_state,
+            move |app, state, event: &TableEvent, cx| {
+                if let TableEvent::SelectRow(row_ix) = event {
+                    let row = state.read(cx).delegate().original_index(*row_ix);
+                    on_select(app, row, cx);
+                }
+            },
+        )
+        .detach();
+    }
+
+    pub fn render(&self) -> impl IntoElement {
+        Table::new(&self.table_state).stripe(false).bordered(false)
+    }
+}
+
+impl ScrollTable for DataTable {
+    fn scroll_offset(&self, cx: &App) -> f32 {
+        table_scroll_offset(&self.table_state, cx)
+    }
+
+    fn set_scroll_offset(&self, offset: f32, cx: &mut App) {
+        set_table_scroll_offset(&self.table_state, offset, cx);
+    }
+}
+
+pub struct DataTableDelegate {
+    rows: Vec<Vec<String>>,
+    order: Vec<usize>,
+    cols: Vec<Column>,
+}
+
+impl DataTableDelegate {
+    pub fn new(cols: Vec<Column>, rows: Vec<Vec<String>>) -> Self {
+        Self {
+            order: (0..rows.len()).collect(),
+            rows,
+            cols,
+        }
+    }
+
+    pub fn original_index(&self, row_ix: usize) -> usize {
+        self.order.get(row_ix).copied().unwrap_or(row_ix)
+    }
+}
+
+// Compare numerically when both cells hold a decimal or 0x-prefixed number
+fn compare_cells(a: &str, b: &str) -> Ordering {
+    fn as_number(s: &str) -> Option<u64> {
+        match s.strip_prefix("0x") {
+            Some(hex) => u64::from_str_radix(hex, 16).ok(),
+            None => s.parse().ok(),
+        }
+    }
+
+    match (as_number(a), as_number(b)) {
+        (Some(a), Some(b)) => a.cmp(&b),
+        _ => a.cmp(b),
+    }
+}
+
+impl TableDelegate for DataTableDelegate {
+    fn columns_count(&self, _: &App) -> usize {
+        self.cols.len()
+    }
+
+    fn rows_count(&self, _: &App) -> usize {
+        self.rows.len()
+    }
+
+    fn column(&self, col_ix: usize, _: &App) -> &Column {
+        &self.cols[col_ix]
+    }
+
+    fn perform_sort(
+        &mut self,
+        col_ix: usize,
+        sort: ColumnSort,
+        _: &mut Window,
+        _: &mut Context<TableState<Self>>,
+    ) {
+        let rows = &self.rows;
+        match sort {
+            ColumnSort::Ascending => self
+                .order
+                .sort_by(|a, b| compare_cells(&rows[*a][col_ix], &rows[*b][col_ix])),
+            ColumnSort::Descending => self
+                .order
+                .sort_by(|a, b| compare_cells(&rows[*b][col_ix], &rows[*a][col_ix])),
+            ColumnSort::Default => self.order.sort(),
+        }
+    }
+
+    fn render_td(
+        &mut self,
+        row_ix: usize,
+        col_ix: usize,
+        _: &mut Window,
+        _: &mut Context<TableState<Self>>,
+    ) -> impl IntoElement {
+        self.rows[self.order[row_ix]]
+            .get(col_ix)
+            .cloned()
+            .unwrap_or_default()
+    }
+}
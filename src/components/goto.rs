@@ -0,0 +1,267 @@
+use gpui::{
+    AppContext, Context, Entity, InteractiveElement, IntoElement, ParentElement, Styled,
+    Subscription, Window, div,
+};
+use gpui_component::{
+    ActiveTheme, Selectable, StyledExt, WindowExt,
+    button::{Button, ButtonVariants},
+    input::{Input, InputEvent, InputState},
+    notification::Notification,
+};
+
+use crate::{
+    CloseGoTo, RouteName, RustDump,
+    pe::address::{AddressSpace, Unmapped},
+};
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum GoToKind {
+    Offset,
+    Rva,
+    Va,
+}
+
+/// Ctrl+G bar for jumping to a file offset, RVA or VA
+pub struct GoToBar {
+    input: Entity<InputState>,
+    kind: GoToKind,
+    _subscription: Subscription,
+}
+
+/// Hex, with or without a 0x prefix, or decimal with a 0n or # prefix
+fn parse_address(text: &str) -> Option<u64> {
+    let text = text.trim();
+    let decimal = ["0n", "0N", "#"]
+        .iter()
+        .find_map(|prefix| text.strip_prefix(prefix));
+    if let Some(digits) = decimal {
+        return digits.parse().ok();
+    }
+    let digits = text
+        .strip_prefix("0x")
+        .or_else(|| text.strip_prefix("0X"))
+        .unwrap_or(text);
+    u64::from_str_radix(digits, 16).ok()
+}
+
+/// File offset and VA that `address` points at, `None` for an RVA or VA without PE headers
+fn resolve(
+    kind: GoToKind,
+    address: u64,
+    space: Option<AddressSpace>,
+    file_size: usize,
+) -> Option<(Result<usize, Unmapped>, Result<u64, Unmapped>)> {
+    match (space, kind) {
+        (space, GoToKind::Offset) => {
+            let offset = usize::try_from(address)
+                .ok()
+                .filter(|offset| *offset < file_size)
+                .ok_or(Unmapped::OutsideFile);
+            let va = match (space, &offset) {
+                (Some(space), Ok(offset)) => space.offset_to_va(*offset),
+                (Some(_), Err(err)) => Err(err.clone()),
+                (None, _) => Err(Unmapped::NotLoaded),
+            };
+            Some((offset, va))
+        }
+        (None, _) => None,
+        (Some(space), GoToKind::Rva) => Some(match u32::try_from(address) {
+            Ok(rva) => (space.rva_to_offset(rva), Ok(space.rva_to_va(rva))),
+            Err(_) => (Err(Unmapped::OutsideImage), Err(Unmapped::OutsideImage)),
+        }),
+        (Some(space), GoToKind::Va) => Some((
+            space.va_to_offset(address),
+            space.va_to_rva(address).map(|_| address),
+        )),
+    }
+}
+
+impl GoToBar {
+    pub fn new(kind: GoToKind, window: &mut Window, cx: &mut Context<RustDump>) -> Self {
+        let input = cx
+            .new(|cx| InputState::new(window, cx).placeholder("Hex address, 0n or # for decimal"));
+        let subscription = cx.subscribe_in(
+            &input,
+            window,
+            |app, _input, event: &InputEvent, window, cx| {
+                if let InputEvent::PressEnter { .. } = event {
+                    app.go_to(window, cx);
+                }
+            },
+        );
+        input.update(cx, |input, cx| input.focus(window, cx));
+
+        Self {
+            input,
+            kind,
+            _subscription: subscription,
+        }
+    }
+
+    pub fn render(&self, cx: &mut Context<RustDump>) -> impl IntoElement {
+        let kind_button = |id: &'static str, label: &'static str, kind: GoToKind| {
+            Button::new(id)
+                .label(label)
+                .selected(self.kind == kind)
+                .on_click(cx.listener(move |app, _event, _window, cx| {
+                    if let Some(goto) = &mut app.goto {
+                        goto.kind = kind;
+                        cx.notify();
+                    }
+                }))
+        };
+
+        div()
+            .h_flex()
+            .key_context("GoToBar")
+            .gap_2()
+            .p_2()
+            .border_b_1()
+            .border_color(cx.theme().sidebar_border)
+            .bg(cx.theme().background)
+            .font_family("Diodrum Cyrillic")
+            .child("Go to")
+            .child(div().w_64().child(Input::new(&self.input)))
+            .child(kind_button("goto_offset", "Offset", GoToKind::Offset))
+            .child(kind_button("goto_rva", "RVA", GoToKind::Rva))
+            .child(kind_button("goto_va", "VA", GoToKind::Va))
+            .child(
+                Button::new("goto_go")
+                    .label("Go")
+                    .on_click(cx.listener(|app, _event, window, cx| app.go_to(window, cx))),
+            )
+            .child(Button::new("goto_close").label("×").on_click(
+                cx.listener(|app, _event, window, cx| app.close_go_to(&CloseGoTo, window, cx)),
+            ))
+    }
+}
+
+impl RustDump {
+    /// Jumps the Hexdump table to the file offset and the Assembly list to the VA of the
+    /// address in the go-to bar
+    pub fn go_to(&mut self, window: &mut Window, cx: &mut Context<RustDump>) {
+        let (Some(goto), Some(tab)) = (&self.goto, self.tabs.get_mut(self.active_tab)) else {
+            return;
+        };
+        let text = goto.input.read(cx).value().to_string();
+        let Some(address) = parse_address(&text) else {
+            window.push_notification(
+                Notification::error(format!("\"{}\" is not an address", text.trim())),
+                cx,
+            );
+            return;
+        };
+
+        let Some((offset, va)) = resolve(goto.kind, address, tab.address_space(), tab.bytes.len())
+        else {
+            window.push_notification(Notification::error("RVAs and VAs need a valid PE file"), cx);
+            return;
+        };
+
+        if let Ok(offset) = offset {
+            if let Some(hexdump) = tab.routes.get_mut(&RouteName::Hexdump) {
+                hexdump.go_to_offset(offset, cx);
+            }
+        }
+        if let Ok(va) = va {
+            if let Some(assembly) = tab.routes.get_mut(&RouteName::Assembly) {
+                assembly.go_to_address(va);
+            }
+        }
+
+        match (offset, va) {
+            (Err(err), Err(_)) => window.push_notification(
+                Notification::error(format!("0x{:X} is {}", address, err)),
+                cx,
+            ),
+            (Err(err), Ok(_)) => {
+                tab.current_route = RouteName::Assembly;
+                window.push_notification(
+                    Notification::warning(format!(
+                        "0x{:X} has no file offset, it is {}",
+                        address, err
+                    )),
+                    cx,
+                );
+            }
+            (Ok(_), _) if tab.current_route == RouteName::Info => {
+                tab.current_route = RouteName::Hexdump;
+            }
+            _ => {}
+        }
+        cx.notify();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pe::address::tests::{section, space};
+
+    #[test]
+    fn hex_addresses() {
+        assert_eq!(parse_address("401000"), Some(0x40_1000));
+        assert_eq!(parse_address(" 0x1F "), Some(0x1F));
+        assert_eq!(parse_address("0XfF"), Some(0xFF));
+        assert_eq!(parse_address("10"), Some(0x10));
+        assert_eq!(parse_address("0x"), None);
+        assert_eq!(parse_address("g00"), None);
+    }
+
+    #[test]
+    fn decimal_addresses() {
+        assert_eq!(parse_address("0n4096"), Some(0x1000));
+        assert_eq!(parse_address("#16"), Some(0x10));
+        assert_eq!(parse_address("0N10"), Some(10));
+        assert_eq!(parse_address("#1F"), None);
+        assert_eq!(parse_address("#"), None);
+    }
+
+    #[test]
+    fn offsets_resolve_without_pe_headers() {
+        assert_eq!(
+            resolve(GoToKind::Offset, 0x10, None, 0x100),
+            Some((Ok(0x10), Err(Unmapped::NotLoaded)))
+        );
+        assert_eq!(
+            resolve(GoToKind::Offset, 0x100, None, 0x100),
+            Some((Err(Unmapped::OutsideFile), Err(Unmapped::NotLoaded)))
+        );
+        assert_eq!(resolve(GoToKind::Rva, 0x10, None, 0x100), None);
+        assert_eq!(resolve(GoToKind::Va, 0x10, None, 0x100), None);
+    }
+
+    #[test]
+    fn offset_rva_and_va_dispatch() {
+        let sections = [
+            section(".text", 0x1000, 0x200, 0x400, 0x200),
+            section(".bss", 0x2000, 0x800, 0, 0),
+        ];
+        let space = space(&sections, 0x1000);
+
+        assert_eq!(
+            resolve(GoToKind::Offset, 0x410, Some(space), 0x600),
+            Some((Ok(0x410), Ok(0x40_1010)))
+        );
+        assert_eq!(
+            resolve(GoToKind::Rva, 0x1010, Some(space), 0x600),
+            Some((Ok(0x410), Ok(0x40_1010)))
+        );
+        assert_eq!(
+            resolve(GoToKind::Va, 0x40_1010, Some(space), 0x600),
+            Some((Ok(0x410), Ok(0x40_1010)))
+        );
+        assert_eq!(
+            resolve(GoToKind::Rva, 0x2004, Some(space), 0x600),
+            Some((Err(Unmapped::ZeroFilled(".bss".to_string())), Ok(0x40_2004)))
+        );
+        assert_eq!(
+            resolve(GoToKind::Va, 0x1010, Some(space), 0x600),
+            Some((Err(Unmapped::BelowImageBase), Err(Unmapped::BelowImageBase)))
+        );
+        assert_eq!(
+            resolve(GoToKind::Rva, 0x1_0000_0000, Some(space), 0x600),
+            Some((Err(Unmapped::OutsideImage), Err(Unmapped::OutsideImage)))
+        );
+    }
+}
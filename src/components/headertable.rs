@@ -1,11 +1,18 @@
-use gpui::{App, AppContext, Context, Entity, IntoElement, Window};
+use gpui::{
+    App, AppContext, Context, Entity, InteractiveElement, IntoElement, ParentElement, SharedString,
+    StatefulInteractiveElement, Window, div,
+};
 use gpui_component::{
     Sizable,
     table::{Column, Table, TableDelegate, TableState},
+    tooltip::Tooltip,
 };
 use serde_json::Value;
 
-use crate::RustDump;
+use crate::{
+    RustDump,
+    components::datatable::{ScrollTable, set_table_scroll_offset, table_scroll_offset},
+};
 
 pub struct HeaderTable {
     pub table_state: Entity<TableState<HeaderTableDelegate>>,
@@ -28,12 +35,49 @@ impl HeaderTable {
     }
 }
 
+impl ScrollTable for HeaderTable {
+    fn scroll_offset(&self, cx: &App) -> f32 {
+        table_scroll_offset(&self.table_state, cx)
+    }
+
+    fn set_scroll_offset(&self, offset: f32, cx: &mut App) {
+        set_table_scroll_offset(&self.table_state, offset, cx);
+    }
+}
+
 #[derive(Debug)]
 pub struct HeaderData {
     pub offset: String,
     pub name: String,
     pub value: Value,
     pub meaning: String,
+    /// Shown over the value and meaning cells, e.g. one line per set flag
+    pub tooltip: Option<String>,
+}
+
+impl HeaderData {
+    pub fn new(offset: usize, name: &str, value: impl Into<Value>, meaning: &str) -> Self {
+        Self {
+            offset: format!("0x{:08X}", offset),
+            name: name.to_string(),
+            value: value.into(),
+            meaning: meaning.to_string(),
+            tooltip: None,
+        }
+    }
+
+    /// A value derived from the file rather than read from a header field
+    pub fn computed(name: &str, value: impl Into<Value>, meaning: &str) -> Self {
+        Self {
+            offset: "-".to_string(),
+            ..Self::new(0, name, value, meaning)
+        }
+    }
+
+    pub fn with_tooltip(mut self, tooltip: String) -> Self {
+        self.tooltip = (!tooltip.is_empty()).then_some(tooltip);
+        self
+    }
 }
 
 #[derive(Debug)]
@@ -87,12 +131,24 @@ impl TableDelegate for HeaderTableDelegate {
         let row = &self.data[row_ix];
         let col = &self.cols[col_ix];
 
-        match col.key.as_ref() {
+        let text = match col.key.as_ref() {
             "offset" => row.offset.to_string(),
             "name" => row.name.clone(),
             "value" => row.value.to_string(),
             "meaning" => row.meaning.clone(),
             _ => "".to_string(),
+        };
+
+        match (&row.tooltip, col.key.as_ref()) {
+            (Some(tooltip), "value" | "meaning") => {
+                let tooltip = SharedString::from(tooltip.clone());
+                div()
+                    .id(("header_cell", row_ix * self.cols.len() + col_ix))
+                    .child(text)
+                    .tooltip(move |window, cx| Tooltip::new(tooltip.clone()).build(window, cx))
+                    .into_any_element()
+            }
+            _ => div().child(text).into_any_element(),
         }
     }
 }
@@ -28,7 +28,10 @@ impl HexviewDelegate {
             .iter()
             .map(|ch| Column::new(ch.to_ascii_lowercase().to_string(), ch.to_string()).width(50.))
             .collect::<Vec<Column>>();
-        cols.insert(0, Column::new("offset", "Offset").movable(false).width(75.));
+        cols.insert(
+            0,
+            Column::new("offset", "Offset").movable(false).width(100.),
+        );
         Self { data, cols }
     }
 }
@@ -85,7 +88,7 @@ impl Hexview {
         data: Vec<u8>,
         window: &mut Window,
         cx: &mut Context<RustDump>,
-        idx_offset: u16,
+        idx_offset: usize,
     ) {
         let chunks = data.chunks(16);
         let hex_data = chunks
@@ -102,17 +105,9 @@ impl Hexview {
                 bt_arr
             })
             .enumerate()
-            .map(|(i, d)| {
-                let mut offset = String::new();
-                (i as u16 * 16 + idx_offset)
-                    .to_le_bytes()
-                    .iter()
-                    .for_each(|b| push_hex(&mut offset, *b));
-
-                HexviewData {
-                    offset,
-                    hex_data: d,
-                }
+            .map(|(i, d)| HexviewData {
+                offset: format!("{:08X}", i * 16 + idx_offset),
+                hex_data: d,
             })
             .collect();
         let delegate = HexviewDelegate::new(hex_data);
@@ -1,4 +1,6 @@
 pub mod asciiview;
+pub mod datatable;
+pub mod goto;
 pub mod headertable;
 pub mod hexview;
 pub mod titlebar;
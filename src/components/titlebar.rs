@@ -7,7 +7,7 @@ use gpui_component::{
     white,
 };
 
-use crate::{RouteName, RustDump};
+use crate::{ExportReport, RouteName, RustDump};
 
 pub struct AppTitlebar {
     custom_button: ButtonCustomVariant,
@@ -38,8 +38,10 @@ impl AppTitlebar {
                         Button::new("info")
                             .child("Info")
                             .on_click(cx.listener(|app, _event, _window, cx| {
-                                app.current_route = RouteName::Info;
-                                cx.notify();
+                                if let Some(tab) = app.tabs.get_mut(app.active_tab) {
+                                    tab.current_route = RouteName::Info;
+                                    cx.notify();
+                                }
                             }))
                             .custom(self.custom_button)
                             .px_6(),
@@ -55,8 +57,10 @@ impl AppTitlebar {
                         Button::new("hexdump")
                             .child("Hexdump")
                             .on_click(cx.listener(|app, _event, _window, cx| {
-                                app.current_route = RouteName::Hexdump;
-                                cx.notify();
+                                if let Some(tab) = app.tabs.get_mut(app.active_tab) {
+                                    tab.current_route = RouteName::Hexdump;
+                                    cx.notify();
+                                }
                             }))
                             .custom(self.custom_button)
                             .px_6(),
@@ -72,8 +76,20 @@ impl AppTitlebar {
                         Button::new("assembly")
                             .child("Assembly")
                             .on_click(cx.listener(|app, _event, _window, cx| {
-                                app.current_route = RouteName::Assembly;
-                                cx.notify();
+                                if let Some(tab) = app.tabs.get_mut(app.active_tab) {
+                                    tab.current_route = RouteName::Assembly;
+                                    cx.notify();
+                                }
+                            }))
+                            .custom(self.custom_button)
+                            .px_6(),
+                    )
+                    .child(div().flex_1())
+                    .child(
+                        Button::new("export_report")
+                            .child("Export report")
+                            .on_click(cx.listener(|app, _event, window, cx| {
+                                app.export_report(&ExportReport, window, cx);
                             }))
                             .custom(self.custom_button)
                             .px_6(),
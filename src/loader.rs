@@ -0,0 +1,203 @@
+use std::{
+    fs::File,
+    io::{self, Read},
+    path::{Path, PathBuf},
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+};
+
+use anyhow::{Context as _, anyhow};
+use gpui::{
+    AnyElement, AsyncWindowContext, Context, IntoElement, ParentElement, SharedString, Styled,
+    Task, WeakEntity, div,
+};
+use gpui_component::{ActiveTheme, StyledExt, button::Button, progress::Progress};
+use pe_parse::PEHeader;
+
+use crate::{
+    CancelLoad, RustDump,
+    pe::directories::{self, Directories},
+    routes::assembly::assembly::Disassembly,
+};
+
+const STAGES: [&str; 4] = [
+    "Reading file",
+    "Parsing headers",
+    "Parsing directories",
+    "Disassembling",
+];
+
+/// Bytes read between checks for a cancelled load
+const READ_CHUNK: u64 = 0x100_0000;
+
+/// Everything the routes need from a file, computed off the UI thread.
+/// Routes take their part out of it while loading.
+pub struct LoadedFile {
+    pub path: PathBuf,
+    /// Shared by the hex dump, the search and the Info views
+    pub bytes: Arc<Vec<u8>>,
+    pub pe_header: anyhow::Result<PEHeader>,
+    /// `None` unless the file is a PE image
+    pub directories: Option<Directories>,
+    pub disassembly: Disassembly,
+}
+
+impl LoadedFile {
+    pub fn pe_header(&self) -> anyhow::Result<&PEHeader> {
+        self.pe_header
+            .as_ref()
+            .map_err(|err| anyhow!("{} is not a valid PE file: {:#}", self.path.display(), err))
+    }
+}
+
+/// A load in progress, dropping it cancels the load. Dropping the task only stops
+/// it at its next await, so `cancelled` also stops the stage running in the background.
+pub struct Loading {
+    pub path: PathBuf,
+    pub stage: usize,
+    cancelled: Arc<AtomicBool>,
+    _task: Task<()>,
+}
+
+impl Drop for Loading {
+    fn drop(&mut self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+}
+
+impl Loading {
+    pub fn new(path: PathBuf, cancelled: Arc<AtomicBool>, task: Task<()>) -> Self {
+        Self {
+            path,
+            stage: 0,
+            cancelled,
+            _task: task,
+        }
+    }
+
+    pub fn render(&self, cx: &mut Context<RustDump>) -> AnyElement {
+        let name = self
+            .path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let percent = self.stage as f32 * 100. / STAGES.len() as f32;
+
+        div()
+            .v_flex()
+            .size_full()
+            .justify_center()
+            .items_center()
+            .gap_2()
+            .bg(cx.theme().background)
+            .font_family(SharedString::from("Diodrum Cyrillic"))
+            .child(format!("Loading {}", name))
+            .child(div().w_1_3().child(Progress::new().value(percent)))
+            .child(format!(
+                "{} ({}/{})",
+                STAGES[self.stage.min(STAGES.len() - 1)],
+                self.stage + 1,
+                STAGES.len()
+            ))
+            .child(
+                Button::new("cancel_load")
+                    .label("Cancel")
+                    .on_click(cx.listener(|app, _event, window, cx| {
+                        app.cancel_load(&CancelLoad, window, cx);
+                    })),
+            )
+            .into_any_element()
+    }
+}
+
+fn set_stage(
+    this: &WeakEntity<RustDump>,
+    stage: usize,
+    cx: &mut AsyncWindowContext,
+) -> anyhow::Result<()> {
+    this.update(cx, |app, cx| {
+        if let Some(loading) = &mut app.loading {
+            loading.stage = stage;
+        }
+        cx.notify();
+    })
+}
+
+/// Reads `path` in chunks, `None` once `cancelled` is set
+fn read_file(path: &Path, cancelled: &AtomicBool) -> io::Result<Option<Vec<u8>>> {
+    let mut file = File::open(path)?;
+    let len = file
+        .metadata()
+        .map_or(0, |metadata| metadata.len() as usize);
+    let mut bytes = Vec::with_capacity(len);
+    loop {
+        if cancelled.load(Ordering::Relaxed) {
+            return Ok(None);
+        }
+        if (&mut file).take(READ_CHUNK).read_to_end(&mut bytes)? == 0 {
+            return Ok(Some(bytes));
+        }
+    }
+}
+
+/// Runs each stage on the background executor, reporting progress to `this`
+/// between stages. Only a failure to read the file fails the whole load.
+/// Stages return early once `cancelled` is set.
+pub async fn load_in_background(
+    this: &WeakEntity<RustDump>,
+    path: PathBuf,
+    cancelled: Arc<AtomicBool>,
+    cx: &mut AsyncWindowContext,
+) -> anyhow::Result<LoadedFile> {
+    let read_path = path.clone();
+    let read_cancelled = cancelled.clone();
+    let bytes = cx
+        .background_spawn(async move { read_file(&read_path, &read_cancelled) })
+        .await
+        .with_context(|| format!("failed to read {}", path.display()))?
+        .ok_or_else(|| anyhow!("load cancelled"))?;
+    let bytes = Arc::new(bytes);
+
+    set_stage(this, 1, cx)?;
+    let header_bytes = bytes.clone();
+    let pe_header = cx
+        .background_spawn(async move {
+            pe_parse::parse_pe_header(&header_bytes[..]).map_err(|err| anyhow!("{:?}", err))
+        })
+        .await;
+    let Ok(header) = &pe_header else {
+        return Ok(LoadedFile {
+            path,
+            bytes,
+            pe_header,
+            directories: None,
+            disassembly: Disassembly::default(),
+        });
+    };
+
+    set_stage(this, 2, cx)?;
+    let (dir_bytes, dir_header, dir_cancelled) = (bytes.clone(), header.clone(), cancelled.clone());
+    let directories = cx
+        .background_spawn(async move {
+            directories::parse_directories(&dir_bytes, &dir_header, &dir_cancelled)
+        })
+        .await
+        .ok_or_else(|| anyhow!("load cancelled"))?;
+
+    set_stage(this, 3, cx)?;
+    let (asm_bytes, asm_header) = (bytes.clone(), header.clone());
+    let disassembly = cx
+        .background_spawn(async move { Disassembly::decode(&asm_bytes, &asm_header, &cancelled) })
+        .await
+        .ok_or_else(|| anyhow!("load cancelled"))?;
+
+    Ok(LoadedFile {
+        path,
+        bytes,
+        pe_header,
+        directories: Some(directories),
+        disassembly,
+    })
+}
@@ -1,79 +1,141 @@
 use std::{
     borrow::Cow,
-    collections::HashMap,
-    path::{Path, PathBuf},
+    collections::VecDeque,
+    path::PathBuf,
+    sync::{Arc, atomic::AtomicBool},
 };
 
 use crate::{
     assets::{CombinedAssets, CustomAssets},
-    components::titlebar::AppTitlebar,
-    routes::{
-        assembly::assembly::Assembly, hexdump::hexdump::Hexdump, info::info::Info,
-        starting::starting::Starting,
+    cli::Cli,
+    components::{
+        goto::{GoToBar, GoToKind},
+        titlebar::AppTitlebar,
     },
+    loader::{LoadedFile, Loading},
+    routes::starting::starting::Starting,
+    session::{Restore, Session},
+    tab::FileTab,
 };
+use anyhow::Context as _;
+use clap::Parser;
 use gpui::{
     AnyElement, App, Application, AssetSource, Bounds, Context, DefiniteLength, Entity,
-    FocusHandle, Focusable, KeyBinding, SharedString, TitlebarOptions, Window, WindowBounds,
-    WindowOptions, actions, div, prelude::*, px, size, transparent_black,
+    ExternalPaths, FocusHandle, Focusable, KeyBinding, SharedString, TitlebarOptions, Window,
+    WindowBounds, WindowOptions, actions, div, prelude::*, px, size, transparent_black,
 };
 use gpui_component::{
-    ActiveTheme, Root, StyledExt, ThemeMode, TitleBar,
+    ActiveTheme, Root, StyledExt, ThemeMode, TitleBar, WindowExt,
     button::{Button, ButtonCustomVariant, ButtonVariants},
     input::{Input, InputState},
+    notification::Notification,
 };
+use serde::{Deserialize, Serialize};
 
 mod assets;
+mod cli;
 mod components;
+mod loader;
+mod pe;
 mod routes;
+mod session;
+mod tab;
 
-actions!(rustdump, [OpenFile]);
+actions!(
+    rustdump,
+    [
+        OpenFile,
+        ExportReport,
+        CancelLoad,
+        CloseTab,
+        NextTab,
+        OpenGoTo,
+        CloseGoTo,
+        Find,
+        FindNext,
+        FindPrevious
+    ]
+);
 
-#[derive(PartialEq, Eq, Hash)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum RouteName {
-    Starting,
     Info,
     Hexdump,
     Assembly,
 }
 
+#[derive(Clone, Serialize, Deserialize)]
 pub enum InfoDisplayPage {
     DOSHeaders,
     DOSStub,
+    RichHeader,
     FileHdr,
     OptHdr,
     SectionHeaders,
     Section(SharedString),
+    Imports,
+    Import(usize),
+    DelayImport(usize),
+    BoundImports,
+    Exports,
+    Resource(usize),
+    Relocations,
+    Tls,
+    Debug,
+    LoadConfig,
+    Exceptions,
+    Security,
+    Clr,
+    ClrTable(usize),
+    ClrUserStrings,
 }
 
 pub trait Route {
     fn render(&self, cx: &mut Context<RustDump>, app: &RustDump) -> AnyElement;
-    fn load(&mut self, cx: &mut Context<RustDump>, window: &mut Window, path: &Path);
+    fn load(
+        &mut self,
+        cx: &mut Context<RustDump>,
+        window: &mut Window,
+        file: &mut LoadedFile,
+    ) -> anyhow::Result<()>;
+    fn go_to_address(&mut self, _va: u64) {}
+    fn go_to_offset(&mut self, _offset: usize, _cx: &mut App) {}
+    /// Called before the Info route shows `page`, to build views that need a window
+    fn open_info_page(
+        &mut self,
+        _page: &InfoDisplayPage,
+        _window: &mut Window,
+        _cx: &mut Context<RustDump>,
+    ) {
+    }
+    /// Vertical scroll position, saved with the session. The Info route scrolls the
+    /// table of the tab's current `page`.
+    fn scroll_offset(&self, _page: &InfoDisplayPage, _cx: &App) -> f32 {
+        0.
+    }
+    fn set_scroll_offset(&mut self, _offset: f32, _page: &InfoDisplayPage, _cx: &mut App) {}
 }
 
 pub struct RustDump {
-    pub current_route: RouteName,
     pub focus_handle: FocusHandle,
-    pub routes: HashMap<RouteName, Box<dyn Route>>,
-    pub curr_file: Option<PathBuf>,
+    pub starting: Starting,
+    pub tabs: Vec<FileTab>,
+    pub active_tab: usize,
+    pub loading: Option<Loading>,
+    pub recent_files: Vec<PathBuf>,
+    pub restore: Option<Restore>,
+    /// Dropped files waiting for the current load, opened one at a time
+    pub queued_paths: VecDeque<PathBuf>,
+    pub goto: Option<GoToBar>,
     pub custom_button: ButtonCustomVariant,
-    pub info_page: InfoDisplayPage,
-    pub expand_nt: bool,
-    pub expand_sct: bool,
-    pub expand_section: bool,
     pub assembly_data: Vec<String>,
     pub titlebar: AppTitlebar,
 }
 
 impl RustDump {
     // Create a new instance with window parameter
-    fn new(cx: &mut Context<Self>, window: &mut Window) -> Self {
-        let mut routes: HashMap<RouteName, Box<dyn Route>> = HashMap::new();
-        routes.insert(RouteName::Starting, Box::new(Starting::new()));
-        routes.insert(RouteName::Info, Box::new(Info::new(window, cx)));
-        routes.insert(RouteName::Hexdump, Box::new(Hexdump::new(window, cx)));
-        routes.insert(RouteName::Assembly, Box::new(Assembly::new(window, cx)));
-
+    fn new(cx: &mut Context<Self>, window: &mut Window, file: Option<PathBuf>) -> Self {
+        let session = Session::load();
         let custom_button = ButtonCustomVariant::new(cx)
             .color(cx.theme().background)
             .foreground(cx.theme().foreground)
@@ -81,33 +143,305 @@ impl RustDump {
             .hover(cx.theme().background)
             .active(cx.theme().accent);
 
-        Self {
-            info_page: InfoDisplayPage::DOSHeaders,
-            routes,
-            current_route: RouteName::Starting,
+        let mut app = Self {
+            starting: Starting::new(),
+            tabs: vec![],
+            active_tab: 0,
+            loading: None,
+            recent_files: session.recent_files,
+            restore: Some(Restore {
+                queue: session.tabs.into(),
+                current: None,
+                active_path: session.active_path,
+                then_open: file,
+            }),
+            queued_paths: VecDeque::new(),
+            goto: None,
             focus_handle: cx.focus_handle(),
-            curr_file: None,
             titlebar: AppTitlebar::new(custom_button.clone()),
             custom_button,
-            expand_section: false,
-            expand_nt: false,
-            expand_sct: false,
             assembly_data: vec![],
+        };
+
+        window.on_window_should_close(cx, {
+            let this = cx.weak_entity();
+            move |_window, cx| {
+                let _ = this.update(cx, |app, cx| app.save_session(cx));
+                true
+            }
+        });
+        app.restore_next(window, cx);
+        app
+    }
+
+    pub fn save_session(&self, cx: &App) {
+        let session = Session {
+            recent_files: self.recent_files.clone(),
+            tabs: self.tabs.iter().map(|tab| tab.session(cx)).collect(),
+            active_path: self.tabs.get(self.active_tab).map(|tab| tab.path.clone()),
+        };
+        if let Err(err) = session.save() {
+            eprintln!("Failed to save session: {:#}", err);
+        }
+    }
+
+    /// Reopens the next saved tab, then the file from the command line, then the dropped
+    /// files. Called whenever a load finishes.
+    fn restore_next(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(restore) = &mut self.restore else {
+            self.open_queued(window, cx);
+            return;
+        };
+        while let Some(saved) = restore.queue.pop_front() {
+            if self.tabs.iter().any(|tab| tab.path == saved.path) {
+                continue;
+            }
+            let path = saved.path.clone();
+            restore.current = Some(saved);
+            self.load_path(path, window, cx);
+            return;
+        }
+
+        let active_path = restore.active_path.take();
+        let then_open = restore.then_open.take();
+        self.restore = None;
+        if let Some(ix) =
+            active_path.and_then(|path| self.tabs.iter().position(|tab| tab.path == path))
+        {
+            self.active_tab = ix;
+        }
+        if let Some(path) = then_open {
+            self.load_path(path, window, cx);
+        } else {
+            self.open_queued(window, cx);
+        }
+        cx.notify();
+    }
+
+    /// Starts loading the next queued file, skipping files that are already open
+    fn open_queued(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        while self.loading.is_none() {
+            let Some(path) = self.queued_paths.pop_front() else {
+                return;
+            };
+            self.load_path(path, window, cx);
+        }
+    }
+
+    /// The tab of the file being shown. Only valid while a file is open, which
+    /// is the case whenever a route view is rendered.
+    pub fn tab(&self) -> &FileTab {
+        &self.tabs[self.active_tab]
+    }
+
+    pub fn tab_mut(&mut self) -> &mut FileTab {
+        &mut self.tabs[self.active_tab]
+    }
+
+    fn close_tab(&mut self, ix: usize, cx: &mut Context<Self>) {
+        if ix >= self.tabs.len() {
+            return;
         }
+        self.tabs.remove(ix);
+        if self.active_tab > ix || self.active_tab >= self.tabs.len() {
+            self.active_tab = self.active_tab.saturating_sub(1);
+        }
+        self.save_session(cx);
+        cx.notify();
+    }
+
+    fn close_active_tab(&mut self, _: &CloseTab, _window: &mut Window, cx: &mut Context<Self>) {
+        self.close_tab(self.active_tab, cx);
+    }
+
+    fn next_tab(&mut self, _: &NextTab, _window: &mut Window, cx: &mut Context<Self>) {
+        if !self.tabs.is_empty() {
+            self.active_tab = (self.active_tab + 1) % self.tabs.len();
+            cx.notify();
+        }
+    }
+
+    /// Loads `path` into a new tab in the background, used by the open dialog, drag and drop
+    /// and the command line. Files that are already open are switched to instead of reloaded.
+    /// Starting another load cancels the current one.
+    fn load_path(&mut self, path: PathBuf, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(ix) = self.tabs.iter().position(|tab| tab.path == path) {
+            self.active_tab = ix;
+            cx.notify();
+            return;
+        }
+
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let task = cx.spawn_in(window, {
+            let path = path.clone();
+            let cancelled = cancelled.clone();
+            async move |this, cx| {
+                let result = loader::load_in_background(&this, path, cancelled, cx).await;
+                let _ = this.update_in(cx, |app, window, cx| app.finish_load(result, window, cx));
+            }
+        });
+        self.loading = Some(Loading::new(path, cancelled, task));
+        cx.notify();
+    }
+
+    fn cancel_load(&mut self, _: &CancelLoad, _window: &mut Window, cx: &mut Context<Self>) {
+        self.restore = None;
+        self.queued_paths.clear();
+        if self.loading.take().is_some() {
+            cx.notify();
+        }
+    }
+
+    /// Hands the loaded file to the routes of a new tab
+    fn finish_load(
+        &mut self,
+        result: anyhow::Result<LoadedFile>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.loading = None;
+        let mut file = match result {
+            Ok(file) => file,
+            Err(err) => {
+                window.push_notification(Notification::error(format!("{:#}", err)), cx);
+                cx.notify();
+                self.restore_next(window, cx);
+                return;
+            }
+        };
+
+        let mut tab = FileTab::new(file.path.clone(), window, cx);
+        tab.pe_header = file.pe_header.as_ref().ok().cloned();
+        if let Some(dirs) = &file.directories {
+            tab.imports = dirs.imports.clone();
+            tab.exports = dirs.exports.clone();
+        }
+        for (name, route) in &mut tab.routes {
+            if let Err(err) = route.load(cx, window, &mut file) {
+                tab.route_errors
+                    .insert(*name, SharedString::from(format!("{:#}", err)));
+            }
+        }
+        tab.bytes = file.bytes;
+        tab.search.load(tab.bytes.clone());
+
+        // Files that are not PE images can still be inspected as raw bytes
+        if let Some(err) = tab.route_errors.get(&RouteName::Info).cloned() {
+            if !tab.route_errors.contains_key(&RouteName::Hexdump) {
+                tab.current_route = RouteName::Hexdump;
+            }
+            window.push_notification(Notification::error(err), cx);
+        }
+
+        let saved = self
+            .restore
+            .as_mut()
+            .and_then(|restore| restore.current.take())
+            .filter(|saved| saved.path == tab.path);
+        if let Some(saved) = saved {
+            tab.restore(saved, window, cx);
+        }
+
+        session::push_recent(&mut self.recent_files, &tab.path);
+        self.tabs.push(tab);
+        self.active_tab = self.tabs.len() - 1;
+        self.save_session(cx);
+
+        cx.notify();
+        self.restore_next(window, cx);
+    }
+
+    fn open_go_to(&mut self, _: &OpenGoTo, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(tab) = self.tabs.get(self.active_tab) else {
+            return;
+        };
+        let kind = match tab.current_route {
+            RouteName::Assembly => GoToKind::Va,
+            _ => GoToKind::Offset,
+        };
+        self.goto = Some(GoToBar::new(kind, window, cx));
+        cx.notify();
+    }
+
+    fn close_go_to(&mut self, _: &CloseGoTo, window: &mut Window, cx: &mut Context<Self>) {
+        self.goto = None;
+        window.focus(&self.focus_handle);
+        cx.notify();
     }
 
     fn open_file(&mut self, _: &OpenFile, window: &mut Window, cx: &mut Context<Self>) {
         let path = rfd::FileDialog::new().pick_file();
         if let Some(path) = path {
-            self.current_route = RouteName::Info;
+            self.load_path(path, window, cx);
+        }
+    }
 
-            for (_, route) in &mut self.routes {
-                route.load(cx, window, &path)
-            }
+    fn drop_files(&mut self, paths: &ExternalPaths, window: &mut Window, cx: &mut Context<Self>) {
+        self.queued_paths.extend(paths.paths().iter().cloned());
+        if self.restore.is_none() {
+            self.open_queued(window, cx);
+        }
+    }
 
-            self.curr_file = Some(path);
+    /// Writes the report of the current tab from its loaded data, on the background executor
+    fn export_report(&mut self, _: &ExportReport, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(tab) = self.tabs.get(self.active_tab) else {
+            return;
+        };
+        let Some(pe_header) = tab.pe_header.clone() else {
+            window.push_notification(Notification::error("Reports need a valid PE file"), cx);
+            return;
+        };
+        let file_name = tab
+            .path
+            .file_stem()
+            .map(|stem| format!("{}.json", stem.to_string_lossy()))
+            .unwrap_or_else(|| "report.json".to_string());
+        let Some(target) = rfd::FileDialog::new()
+            .add_filter("JSON", &["json"])
+            .set_file_name(file_name)
+            .save_file()
+        else {
+            return;
+        };
 
-            cx.notify();
+        let file = tab.path.display().to_string();
+        let bytes = tab.bytes.clone();
+        let imports = tab.imports.clone();
+        let exports = tab.exports.clone();
+        cx.spawn_in(window, async move |this, cx| {
+            let written = target.clone();
+            let result = cx
+                .background_spawn(async move {
+                    let report =
+                        pe::report::build_report(&file, &bytes, &pe_header, imports, exports);
+                    let json = serde_json::to_string_pretty(&report)?;
+                    std::fs::write(&written, json)
+                        .with_context(|| format!("failed to write {}", written.display()))
+                })
+                .await;
+            let _ = this.update_in(cx, |_app, window, cx| {
+                let notification = match result {
+                    Ok(()) => {
+                        Notification::success(format!("Report exported to {}", target.display()))
+                    }
+                    Err(err) => Notification::error(format!("Failed to export report: {:#}", err)),
+                };
+                window.push_notification(notification, cx);
+            });
+        })
+        .detach();
+    }
+}
+
+impl RustDump {
+    fn render_current_route(&self, cx: &mut Context<Self>) -> AnyElement {
+        if let Some(loading) = &self.loading {
+            return loading.render(cx);
+        }
+        match self.tabs.get(self.active_tab) {
+            Some(tab) => tab.render(cx, self),
+            None => self.starting.render(cx, self),
         }
     }
 }
@@ -125,19 +459,46 @@ impl Render for RustDump {
             .track_focus(&self.focus_handle)
             .key_context("rustdump")
             .on_action(cx.listener(Self::open_file))
+            .on_action(cx.listener(Self::export_report))
+            .on_action(cx.listener(Self::cancel_load))
+            .on_action(cx.listener(Self::close_active_tab))
+            .on_action(cx.listener(Self::next_tab))
+            .on_action(cx.listener(Self::open_go_to))
+            .on_action(cx.listener(Self::close_go_to))
+            .on_action(cx.listener(Self::find))
+            .on_action(cx.listener(Self::find_next))
+            .on_action(cx.listener(Self::find_previous))
+            .on_drop(cx.listener(Self::drop_files))
+            .drag_over::<ExternalPaths>(|style, _, _, cx| {
+                style.border_2().border_color(cx.theme().primary)
+            })
             .size_full()
             .text_color(cx.theme().foreground)
             .child(self.titlebar.render(window, cx))
+            .when(!self.tabs.is_empty(), |this| {
+                this.child(self.render_tab_bar(cx))
+            })
+            .children(self.goto.as_ref().map(|goto| goto.render(cx)))
             .child(
                 div()
                     .v_flex()
                     .size_full()
-                    .child(self.routes[&self.current_route].render(cx, self)),
+                    .child(self.render_current_route(cx)),
             )
+            .children(Root::render_notification_layer(window, cx))
     }
 }
 
 fn main() {
+    let cli = Cli::parse();
+    if let Some(command) = cli.command {
+        if let Err(err) = cli::run(command) {
+            eprintln!("error: {:#}", err);
+            std::process::exit(1);
+        }
+        return;
+    }
+
     let assets = CombinedAssets::new();
     assets.load("icons/file-text.svg").unwrap().unwrap();
     assets.load("icons/file-spreadsheet.svg").unwrap().unwrap();
@@ -148,7 +509,18 @@ fn main() {
         .unwrap();
     Application::new().with_assets(assets).run(|cx: &mut App| {
         let bounds = Bounds::centered(None, size(px(1920.), px(1080.)), cx);
-        cx.bind_keys(vec![KeyBinding::new("ctrl-o", OpenFile, None)]);
+        cx.bind_keys(vec![
+            KeyBinding::new("ctrl-o", OpenFile, None),
+            KeyBinding::new("ctrl-e", ExportReport, None),
+            KeyBinding::new("escape", CancelLoad, None),
+            KeyBinding::new("ctrl-w", CloseTab, None),
+            KeyBinding::new("ctrl-tab", NextTab, None),
+            KeyBinding::new("ctrl-g", OpenGoTo, None),
+            KeyBinding::new("escape", CloseGoTo, Some("GoToBar")),
+            KeyBinding::new("ctrl-f", Find, None),
+            KeyBinding::new("f3", FindNext, None),
+            KeyBinding::new("shift-f3", FindPrevious, None),
+        ]);
 
         let _ = cx.text_system().add_fonts(vec![font]);
 
@@ -165,7 +537,7 @@ fn main() {
                     ..Default::default()
                 },
                 |window, cx| {
-                    let view = cx.new(|cx| RustDump::new(cx, window));
+                    let view = cx.new(|cx| RustDump::new(cx, window, cli.file));
                     cx.new(|cx| Root::new(view, window, cx))
                 },
             )
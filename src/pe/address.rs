@@ -0,0 +1,402 @@
+use std::fmt;
+
+use pe_parse::{OptionalHeaders, PEHeader, SectionHeader};
+
+/// Smallest section alignment at which the loader maps sections page by page
+const PAGE_SIZE: u32 = 0x1000;
+/// Offset of CheckSum in both optional header layouts
+const CHECKSUM_OFFSET: usize = 64;
+const DATA_DIRECTORY_OFFSET32: usize = 96;
+const DATA_DIRECTORY_OFFSET64: usize = 112;
+const DATA_DIRECTORY_SIZE: usize = 8;
+/// PointerToRawData is rounded down to this by the loader regardless of FileAlignment
+const RAW_POINTER_ALIGNMENT: u32 = 0x200;
+
+/// Why an address has no counterpart in the other address space
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Unmapped {
+    /// VA below the image base
+    BelowImageBase,
+    /// Not covered by the headers or any section
+    OutsideImage,
+    /// Virtual part of a section past its raw data, zero filled by the loader
+    ZeroFilled(String),
+    /// Past the end of the file
+    OutsideFile,
+    /// File bytes the loader does not map, e.g. the overlay or padding between sections
+    NotLoaded,
+}
+
+impl fmt::Display for Unmapped {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Unmapped::BelowImageBase => write!(f, "below the image base"),
+            Unmapped::OutsideImage => write!(f, "outside the image"),
+            Unmapped::ZeroFilled(section) => {
+                write!(f, "zero filled part of {} with no file data", section)
+            }
+            Unmapped::OutsideFile => write!(f, "past the end of the file"),
+            Unmapped::NotLoaded => write!(f, "not mapped by the loader"),
+        }
+    }
+}
+
+/// How the loader lays out the file in memory, built from the parsed headers.
+/// All conversions between file offsets, RVAs and VAs go through this.
+#[derive(Debug, Clone, Copy)]
+pub struct AddressSpace<'a> {
+    pub image_base: u64,
+    pub section_alignment: u32,
+    pub file_alignment: u32,
+    pub size_of_headers: u32,
+    pub size_of_image: u32,
+    pub e_lfanew: usize,
+    pub is_pe64: bool,
+    pub size_of_optional_header: usize,
+    /// Offsets at or past this are not in the file
+    pub file_size: usize,
+    sections: &'a [SectionHeader],
+}
+
+/// A section as the loader maps it
+struct Mapped<'a> {
+    sct: &'a SectionHeader,
+    raw_offset: u32,
+    /// Bytes of file data mapped at the start of the section
+    raw_size: u32,
+    virtual_size: u32,
+}
+
+fn align_up(value: u32, alignment: u32) -> u32 {
+    if alignment == 0 {
+        return value;
+    }
+    let aligned = (value as u64).div_ceil(alignment as u64) * alignment as u64;
+    aligned.min(u32::MAX as u64) as u32
+}
+
+fn section_name(sct: &SectionHeader) -> String {
+    String::from_utf8_lossy(&sct.name)
+        .trim_end_matches('\0')
+        .to_string()
+}
+
+impl<'a> AddressSpace<'a> {
+    pub fn new(pe_header: &'a PEHeader) -> Self {
+        let (section_alignment, file_alignment, size_of_headers, size_of_image) =
+            match &pe_header.nt_header.optional_headers {
+                OptionalHeaders::OptionalHeaders32(opt32) => (
+                    opt32.section_alignment,
+                    opt32.file_alignment,
+                    opt32.size_of_headers,
+                    opt32.size_of_image,
+                ),
+                OptionalHeaders::OptionalHeaders64(opt64) => (
+                    opt64.section_alignment,
+                    opt64.file_alignment,
+                    opt64.size_of_headers,
+                    opt64.size_of_image,
+                ),
+            };
+
+        Self {
+            image_base: super::image_base(pe_header),
+            section_alignment,
+            file_alignment,
+            size_of_headers,
+            size_of_image,
+            e_lfanew: pe_header.dos_header.e_lfanew as usize,
+            is_pe64: super::is_pe64(pe_header),
+            size_of_optional_header: pe_header
+                .nt_header
+                .image_file_header
+                .size_of_optional_header as usize,
+            file_size: usize::MAX,
+            sections: &pe_header.section_headers,
+        }
+    }
+
+    pub fn with_file_size(mut self, file_size: usize) -> Self {
+        self.file_size = file_size;
+        self
+    }
+
+    /// Start of the NT headers, where the PE signature is
+    pub fn nt_headers_offset(&self) -> usize {
+        self.e_lfanew
+    }
+
+    /// Past the signature and the file header
+    pub fn optional_header_offset(&self) -> usize {
+        self.e_lfanew + 24
+    }
+
+    pub fn checksum_offset(&self) -> usize {
+        self.optional_header_offset() + CHECKSUM_OFFSET
+    }
+
+    /// Offset of the data directory entry at `index`
+    pub fn data_directory_offset(&self, index: usize) -> usize {
+        let table = if self.is_pe64 {
+            DATA_DIRECTORY_OFFSET64
+        } else {
+            DATA_DIRECTORY_OFFSET32
+        };
+        self.optional_header_offset() + table + index * DATA_DIRECTORY_SIZE
+    }
+
+    /// First section header, right after the optional header
+    pub fn section_table_offset(&self) -> usize {
+        self.optional_header_offset() + self.size_of_optional_header
+    }
+
+    /// Below page alignment the image is mapped flat, RVAs equal file offsets
+    fn is_flat(&self) -> bool {
+        self.section_alignment < PAGE_SIZE
+    }
+
+    fn mapped_sections(&self) -> impl DoubleEndedIterator<Item = Mapped<'a>> + '_ {
+        self.sections.iter().map(|sct| {
+            let virtual_size = if sct.virtual_size == 0 {
+                sct.size_of_raw_data
+            } else {
+                sct.virtual_size
+            };
+            let virtual_size = align_up(virtual_size, self.section_alignment);
+            let raw_offset = sct.pointer_to_raw_data & !(RAW_POINTER_ALIGNMENT - 1);
+            let raw_size = if sct.pointer_to_raw_data == 0 {
+                0
+            } else {
+                align_up(sct.size_of_raw_data, self.file_alignment).min(virtual_size)
+            };
+            Mapped {
+                sct,
+                raw_offset,
+                raw_size,
+                virtual_size,
+            }
+        })
+    }
+
+    fn in_file(&self, offset: u64) -> Result<usize, Unmapped> {
+        usize::try_from(offset)
+            .ok()
+            .filter(|offset| *offset < self.file_size)
+            .ok_or(Unmapped::OutsideFile)
+    }
+
+    pub fn rva_to_va(&self, rva: u32) -> u64 {
+        self.image_base.wrapping_add(rva as u64)
+    }
+
+    pub fn va_to_rva(&self, va: u64) -> Result<u32, Unmapped> {
+        let rva = va
+            .checked_sub(self.image_base)
+            .ok_or(Unmapped::BelowImageBase)?;
+        u32::try_from(rva).map_err(|_| Unmapped::OutsideImage)
+    }
+
+    pub fn rva_to_offset(&self, rva: u32) -> Result<usize, Unmapped> {
+        if self.is_flat() {
+            return self.in_file(rva as u64);
+        }
+
+        let mapped = self.mapped_sections().rev().find(|mapped| {
+            rva >= mapped.sct.virtual_address
+                && rva - mapped.sct.virtual_address < mapped.virtual_size
+        });
+        if let Some(mapped) = mapped {
+            let delta = rva - mapped.sct.virtual_address;
+            if delta >= mapped.raw_size {
+                return Err(Unmapped::ZeroFilled(section_name(mapped.sct)));
+            }
+            return self.in_file(mapped.raw_offset as u64 + delta as u64);
+        }
+
+        // The headers are mapped at the start of the image, the rest of their page is zero
+        if rva < self.size_of_headers {
+            return self.in_file(rva as u64);
+        }
+        if rva < align_up(self.size_of_headers, self.section_alignment) {
+            return Err(Unmapped::ZeroFilled("the headers".to_string()));
+        }
+        Err(Unmapped::OutsideImage)
+    }
+
+    /// Every RVA the file offset is mapped at, sections can share raw data
+    pub fn offset_to_rvas(&self, offset: usize) -> Vec<u32> {
+        let Ok(offset) = u32::try_from(offset) else {
+            return vec![];
+        };
+        if self.is_flat() {
+            return vec![offset];
+        }
+
+        let mut rvas: Vec<u32> = self
+            .mapped_sections()
+            .filter(|mapped| {
+                offset >= mapped.raw_offset && offset - mapped.raw_offset < mapped.raw_size
+            })
+            .filter_map(|mapped| {
+                mapped
+                    .sct
+                    .virtual_address
+                    .checked_add(offset - mapped.raw_offset)
+            })
+            .collect();
+        if offset < self.size_of_headers {
+            rvas.insert(0, offset);
+        }
+        rvas
+    }
+
+    pub fn offset_to_rva(&self, offset: usize) -> Result<u32, Unmapped> {
+        if offset >= self.file_size {
+            return Err(Unmapped::OutsideFile);
+        }
+        self.offset_to_rvas(offset)
+            .first()
+            .copied()
+            .ok_or(Unmapped::NotLoaded)
+    }
+
+    pub fn va_to_offset(&self, va: u64) -> Result<usize, Unmapped> {
+        self.rva_to_offset(self.va_to_rva(va)?)
+    }
+
+    pub fn offset_to_va(&self, offset: usize) -> Result<u64, Unmapped> {
+        Ok(self.rva_to_va(self.offset_to_rva(offset)?))
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    pub(crate) fn section(
+        name: &str,
+        virtual_address: u32,
+        virtual_size: u32,
+        pointer_to_raw_data: u32,
+        size_of_raw_data: u32,
+    ) -> SectionHeader {
+        let mut name_bytes = [0u8; 8];
+        name_bytes[..name.len()].copy_from_slice(name.as_bytes());
+        SectionHeader {
+            name: name_bytes,
+            virtual_size,
+            virtual_address,
+            size_of_raw_data,
+            pointer_to_raw_data,
+            pointer_to_relocations: 0,
+            pointer_to_linenumbers: 0,
+            number_of_relocations: 0,
+            number_of_linenumbers: 0,
+            characteristics: 0,
+        }
+    }
+
+    pub(crate) fn space(sections: &[SectionHeader], section_alignment: u32) -> AddressSpace<'_> {
+        AddressSpace {
+            image_base: 0x40_0000,
+            section_alignment,
+            file_alignment: 0x200,
+            size_of_headers: 0x400,
+            size_of_image: 0x4000,
+            e_lfanew: 0x80,
+            is_pe64: false,
+            size_of_optional_header: 0xE0,
+            file_size: 0x10000,
+            sections,
+        }
+    }
+
+    #[test]
+    fn headers_are_mapped_into_the_image() {
+        let sections = [section(".text", 0x1000, 0x200, 0x400, 0x200)];
+        let space = space(&sections, 0x1000);
+
+        assert_eq!(space.rva_to_offset(0x3C), Ok(0x3C));
+        assert_eq!(space.offset_to_rva(0x3C), Ok(0x3C));
+        assert_eq!(
+            space.rva_to_offset(0x800),
+            Err(Unmapped::ZeroFilled("the headers".to_string()))
+        );
+    }
+
+    #[test]
+    fn later_section_wins_where_sections_overlap() {
+        let sections = [
+            section(".a", 0x1000, 0x2000, 0x400, 0x200),
+            section(".b", 0x2000, 0x1000, 0x600, 0x200),
+        ];
+        let space = space(&sections, 0x1000);
+
+        assert_eq!(space.rva_to_offset(0x2010), Ok(0x610));
+        assert_eq!(space.rva_to_offset(0x1010), Ok(0x410));
+    }
+
+    #[test]
+    fn shared_raw_data_maps_to_every_section() {
+        let sections = [
+            section(".a", 0x1000, 0x200, 0x400, 0x200),
+            section(".b", 0x2000, 0x200, 0x400, 0x200),
+        ];
+        let space = space(&sections, 0x1000);
+
+        assert_eq!(space.offset_to_rvas(0x410), vec![0x1010, 0x2010]);
+    }
+
+    #[test]
+    fn zero_raw_size_section_is_zero_filled() {
+        let sections = [
+            section(".text", 0x1000, 0x200, 0x400, 0x200),
+            section(".bss", 0x2000, 0x800, 0, 0),
+        ];
+        let space = space(&sections, 0x1000);
+
+        assert_eq!(
+            space.rva_to_offset(0x2004),
+            Err(Unmapped::ZeroFilled(".bss".to_string()))
+        );
+        assert_eq!(space.offset_to_rva(0x600), Err(Unmapped::NotLoaded));
+    }
+
+    #[test]
+    fn raw_data_past_virtual_size_is_not_mapped() {
+        // SizeOfRawData is clamped to the section alignment of the virtual size
+        let sections = [section(".text", 0x1000, 0x10, 0x400, 0x2000)];
+        let space = space(&sections, 0x1000);
+
+        assert_eq!(space.rva_to_offset(0x1FFF), Ok(0x13FF));
+        assert_eq!(space.rva_to_offset(0x2000), Err(Unmapped::OutsideImage));
+    }
+
+    #[test]
+    fn low_alignment_images_are_mapped_flat() {
+        let sections = [section(".text", 0x300, 0x100, 0x200, 0x100)];
+        let space = space(&sections, 0x200);
+
+        assert_eq!(space.rva_to_offset(0x310), Ok(0x310));
+        assert_eq!(space.offset_to_rva(0x210), Ok(0x210));
+        assert_eq!(space.rva_to_offset(0x20000), Err(Unmapped::OutsideFile));
+    }
+
+    #[test]
+    fn virtual_address_overflow_is_skipped() {
+        let sections = [section(".evil", u32::MAX - 0x10, 0x200, 0x400, 0x200)];
+        let space = space(&sections, 0x1000);
+
+        assert_eq!(space.offset_to_rvas(0x500), Vec::<u32>::new());
+        assert_eq!(space.offset_to_rva(0x500), Err(Unmapped::NotLoaded));
+    }
+
+    #[test]
+    fn va_below_image_base_is_reported() {
+        let sections = [];
+        let space = space(&sections, 0x1000);
+
+        assert_eq!(space.va_to_rva(0x1000), Err(Unmapped::BelowImageBase));
+        assert_eq!(space.va_to_rva(0x40_1000), Ok(0x1000));
+    }
+}
@@ -0,0 +1,402 @@
+use std::collections::HashMap;
+
+use md5::Md5;
+use pe_parse::PEHeader;
+use serde::Serialize;
+use sha1::Sha1;
+use sha2::{Digest, Sha256, Sha384, Sha512};
+
+use crate::pe::{
+    self, DIR_SECURITY,
+    address::AddressSpace,
+    der::{self, TAG_INTEGER, TAG_OCTET_STRING, TAG_SEQUENCE, TAG_SET, Tlv},
+    hex, read_u16, read_u32,
+};
+
+pub const WIN_CERT_TYPE_PKCS_SIGNED_DATA: u16 = 0x0002;
+
+const OID_SIGNED_DATA: &str = "1.2.840.113549.1.7.2";
+const OID_SIGNING_TIME: &str = "1.2.840.113549.1.9.5";
+const OID_COUNTERSIGNATURE: &str = "1.2.840.113549.1.9.6";
+const OID_RFC3161_TIMESTAMP: &str = "1.3.6.1.4.1.311.3.3.1";
+const OID_NESTED_SIGNATURE: &str = "1.3.6.1.4.1.311.2.4.1";
+/// Dual signed binaries nest one level deep, anything past this is malformed
+const MAX_NESTING: usize = 4;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct WinCertificate {
+    pub offset: usize,
+    pub length: u32,
+    pub revision: u16,
+    pub certificate_type: u16,
+    pub signatures: Vec<Signature>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Signature {
+    pub digest_algorithm: String,
+    pub signed_digest: String,
+    pub computed_digest: Option<String>,
+    pub signer: Option<String>,
+    pub signer_issuer: String,
+    pub signer_serial: String,
+    pub signing_time: Option<String>,
+    pub timestamp: Option<String>,
+    pub certificates: Vec<CertificateInfo>,
+}
+
+impl Signature {
+    pub fn digest_matches(&self) -> Option<bool> {
+        self.computed_digest
+            .as_ref()
+            .map(|computed| *computed == self.signed_digest)
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CertificateInfo {
+    pub subject: String,
+    pub issuer: String,
+    pub serial: String,
+    pub not_before: String,
+    pub not_after: String,
+}
+
+pub fn algorithm_name(oid: &str) -> &str {
+    match oid {
+        "1.2.840.113549.2.5" => "MD5",
+        "1.3.14.3.2.26" => "SHA1",
+        "2.16.840.1.101.3.4.2.1" => "SHA256",
+        "2.16.840.1.101.3.4.2.2" => "SHA384",
+        "2.16.840.1.101.3.4.2.3" => "SHA512",
+        other => other,
+    }
+}
+
+fn hash_ranges<D: Digest>(bytes: &[u8], ranges: &[(usize, usize)]) -> String {
+    let mut hasher = D::new();
+    for (start, end) in ranges {
+        if let Some(chunk) = bytes.get(*start..*end) {
+            hasher.update(chunk);
+        }
+    }
+    hex(&hasher.finalize())
+}
+
+/// Computes the Authenticode image hash, skipping the checksum, the security
+/// directory entry and the certificate table itself
+pub fn authenticode_digest(bytes: &[u8], pe_header: &PEHeader, algorithm: &str) -> Option<String> {
+    let space = AddressSpace::new(pe_header);
+    let checksum = space.checksum_offset();
+    let security_entry = space.data_directory_offset(DIR_SECURITY);
+
+    let (cert_start, cert_end) = match pe::data_directory(pe_header, DIR_SECURITY) {
+        Some(dir) => (
+            dir.virtual_address as usize,
+            dir.virtual_address as usize + dir.size as usize,
+        ),
+        None => (bytes.len(), bytes.len()),
+    };
+    if checksum + 4 > security_entry || security_entry + 8 > cert_start.min(bytes.len()) {
+        return None;
+    }
+
+    let ranges = [
+        (0, checksum),
+        (checksum + 4, security_entry),
+        (security_entry + 8, cert_start.min(bytes.len())),
+        (cert_end.min(bytes.len()), bytes.len()),
+    ];
+    match algorithm {
+        "MD5" => Some(hash_ranges::<Md5>(bytes, &ranges)),
+        "SHA1" => Some(hash_ranges::<Sha1>(bytes, &ranges)),
+        "SHA256" => Some(hash_ranges::<Sha256>(bytes, &ranges)),
+        "SHA384" => Some(hash_ranges::<Sha384>(bytes, &ranges)),
+        "SHA512" => Some(hash_ranges::<Sha512>(bytes, &ranges)),
+        _ => None,
+    }
+}
+
+/// Image digests by algorithm name. Every signature hashes the same image, so each
+/// algorithm is computed once per file however many signatures use it.
+struct DigestCache<'a> {
+    bytes: &'a [u8],
+    pe_header: &'a PEHeader,
+    digests: HashMap<String, Option<String>>,
+}
+
+impl<'a> DigestCache<'a> {
+    fn new(bytes: &'a [u8], pe_header: &'a PEHeader) -> Self {
+        Self {
+            bytes,
+            pe_header,
+            digests: HashMap::new(),
+        }
+    }
+
+    fn digest(&mut self, algorithm: &str) -> Option<String> {
+        self.digests
+            .entry(algorithm.to_string())
+            .or_insert_with(|| authenticode_digest(self.bytes, self.pe_header, algorithm))
+            .clone()
+    }
+}
+
+fn parse_certificate(cert: &Tlv) -> Option<CertificateInfo> {
+    let tbs = *cert.children().first()?;
+    let mut fields = tbs.children();
+    // Version is an optional explicit [0] field
+    if fields.first()?.is_context(0) {
+        fields.remove(0);
+    }
+    let validity = fields.get(3)?.children();
+    Some(CertificateInfo {
+        serial: fields.first()?.integer_hex(),
+        issuer: der::format_name(fields.get(2)?),
+        not_before: validity.first()?.time().unwrap_or_default(),
+        not_after: validity.get(1)?.time().unwrap_or_default(),
+        subject: der::format_name(fields.get(4)?),
+    })
+}
+
+/// Finds an attribute by OID inside a SET OF Attribute
+fn find_attribute<'a>(attrs: &Tlv<'a>, oid: &str) -> Option<Tlv<'a>> {
+    attrs.children().into_iter().find_map(|attr| {
+        let parts = attr.children();
+        (parts.first()?.oid()? == oid).then(|| parts.get(1).copied())?
+    })
+}
+
+fn signing_time(signer_info: &Tlv) -> Option<String> {
+    let signed_attrs = signer_info
+        .children()
+        .into_iter()
+        .find(|child| child.is_context(0))?;
+    let values = find_attribute(&signed_attrs, OID_SIGNING_TIME)?;
+    values.children().first()?.time()
+}
+
+/// Reads genTime from an RFC 3161 timestamp token
+fn rfc3161_time(token: &Tlv) -> Option<String> {
+    let signed_data = *token.children().get(1)?.children().first()?;
+    let encap = *signed_data.children().get(2)?;
+    let octets = *encap.children().get(1)?.children().first()?;
+    let (tst_info, _) = der::parse(octets.content)?;
+    tst_info.children().get(4)?.time()
+}
+
+fn timestamp(signer_info: &Tlv) -> Option<String> {
+    let unsigned_attrs = signer_info
+        .children()
+        .into_iter()
+        .find(|child| child.is_context(1))?;
+    if let Some(values) = find_attribute(&unsigned_attrs, OID_COUNTERSIGNATURE) {
+        return signing_time(values.children().first()?);
+    }
+    let values = find_attribute(&unsigned_attrs, OID_RFC3161_TIMESTAMP)?;
+    rfc3161_time(values.children().first()?)
+}
+
+/// Decodes a PKCS#7 ContentInfo holding Authenticode SignedData, following nested
+/// signatures up to `MAX_NESTING` levels below `depth`
+fn parse_signed_data(
+    content_info: &Tlv,
+    depth: usize,
+    digests: &mut DigestCache,
+    signatures: &mut Vec<Signature>,
+) -> Option<()> {
+    let parts = content_info.children();
+    if parts.first()?.oid()? != OID_SIGNED_DATA {
+        return None;
+    }
+    let signed_data = *parts.get(1)?.children().first()?;
+    let fields = signed_data.children();
+
+    // SpcIndirectDataContent holds the signed image digest
+    let encap = fields.get(2)?.children();
+    let indirect = *encap.get(1)?.children().first()?;
+    let digest_info = *indirect.children().get(1)?;
+    let digest_parts = digest_info.children();
+    let algorithm_oid = digest_parts.first()?.children().first()?.oid()?;
+    let digest_algorithm = algorithm_name(&algorithm_oid).to_string();
+    let signed_digest = digest_parts
+        .get(1)
+        .filter(|octets| octets.tag == TAG_OCTET_STRING)
+        .map(|octets| hex(octets.content))?;
+
+    let certificates: Vec<CertificateInfo> = fields
+        .iter()
+        .find(|field| field.is_context(0))
+        .map(|set| set.children())
+        .unwrap_or_default()
+        .iter()
+        .filter(|cert| cert.tag == TAG_SEQUENCE)
+        .filter_map(parse_certificate)
+        .collect();
+
+    let signer_info = *fields
+        .iter()
+        .rfind(|field| field.tag == TAG_SET)?
+        .children()
+        .first()?;
+    let signer_fields = signer_info.children();
+    let sid = signer_fields.get(1)?.children();
+    let signer_issuer = der::format_name(sid.first()?);
+    let signer_serial = sid
+        .get(1)
+        .filter(|serial| serial.tag == TAG_INTEGER)
+        .map(|serial| serial.integer_hex())
+        .unwrap_or_default();
+    let signer = certificates
+        .iter()
+        .find(|cert| cert.serial == signer_serial && cert.issuer == signer_issuer)
+        .map(|cert| cert.subject.clone());
+
+    signatures.push(Signature {
+        computed_digest: digests.digest(&digest_algorithm),
+        digest_algorithm,
+        signed_digest,
+        signer,
+        signer_issuer,
+        signer_serial,
+        signing_time: signing_time(&signer_info),
+        timestamp: timestamp(&signer_info),
+        certificates,
+    });
+
+    // Dual signed binaries nest further signatures in an unsigned attribute
+    if depth >= MAX_NESTING {
+        return Some(());
+    }
+    let nested = signer_fields
+        .iter()
+        .find(|field| field.is_context(1))
+        .and_then(|unsigned_attrs| find_attribute(unsigned_attrs, OID_NESTED_SIGNATURE));
+    if let Some(values) = nested {
+        for nested in values.children() {
+            parse_signed_data(&nested, depth + 1, digests, signatures);
+        }
+    }
+
+    Some(())
+}
+
+/// Parses the certificate table, whose directory entry holds a file offset rather than an RVA
+pub fn parse_certificates(bytes: &[u8], pe_header: &PEHeader) -> Vec<WinCertificate> {
+    let Some(dir) = pe::data_directory(pe_header, DIR_SECURITY) else {
+        return vec![];
+    };
+    let start = dir.virtual_address as usize;
+    let end = start.saturating_add(dir.size as usize).min(bytes.len());
+
+    let mut digests = DigestCache::new(bytes, pe_header);
+    let mut certificates = vec![];
+    let mut offset = start;
+    while offset + 8 <= end {
+        let (Some(length), Some(revision), Some(certificate_type)) = (
+            read_u32(bytes, offset),
+            read_u16(bytes, offset + 4),
+            read_u16(bytes, offset + 6),
+        ) else {
+            break;
+        };
+        if length < 8 {
+            break;
+        }
+
+        let mut signatures = vec![];
+        let body_end = (offset + length as usize).min(end);
+        let content_info = der::parse(&bytes[offset + 8..body_end])
+            .filter(|_| certificate_type == WIN_CERT_TYPE_PKCS_SIGNED_DATA);
+        if let Some((content_info, _)) = content_info {
+            parse_signed_data(&content_info, 0, &mut digests, &mut signatures);
+        }
+
+        certificates.push(WinCertificate {
+            offset,
+            length,
+            revision,
+            certificate_type,
+            signatures,
+        });
+
+        // Entries are padded to 8 byte boundaries
+        offset += (length as usize).next_multiple_of(8);
+    }
+
+    certificates
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tlv(tag: u8, content: &[u8]) -> Vec<u8> {
+        let mut out = vec![tag, content.len() as u8];
+        out.extend_from_slice(content);
+        out
+    }
+
+    fn signer_info(signed_attrs: &[u8]) -> Vec<u8> {
+        let mut content = tlv(TAG_INTEGER, &[1]);
+        content.extend(tlv(0xA0, signed_attrs));
+        tlv(TAG_SEQUENCE, &content)
+    }
+
+    /// 1.2.840.113549.1.9.5
+    const SIGNING_TIME_OID: [u8; 9] = [0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x09, 0x05];
+
+    fn signing_time_attr(time: &[u8]) -> Vec<u8> {
+        let mut attr = tlv(der::TAG_OID, &SIGNING_TIME_OID);
+        attr.extend(tlv(TAG_SET, time));
+        tlv(TAG_SEQUENCE, &attr)
+    }
+
+    fn parsed_signing_time(encoded: &[u8]) -> Option<String> {
+        let (info, _) = der::parse(encoded)?;
+        signing_time(&info)
+    }
+
+    #[test]
+    fn signing_time_from_utc_time() {
+        let time = tlv(der::TAG_UTC_TIME, b"230115103000Z");
+        let encoded = signer_info(&signing_time_attr(&time));
+        assert_eq!(
+            parsed_signing_time(&encoded).as_deref(),
+            Some("2023-01-15 10:30:00 UTC")
+        );
+    }
+
+    #[test]
+    fn signing_time_from_generalized_time() {
+        let time = tlv(der::TAG_GENERALIZED_TIME, b"20510203040506Z");
+        let encoded = signer_info(&signing_time_attr(&time));
+        assert_eq!(
+            parsed_signing_time(&encoded).as_deref(),
+            Some("2051-02-03 04:05:06 UTC")
+        );
+    }
+
+    #[test]
+    fn signing_time_skips_other_attributes() {
+        // 1.2.840.113549.1.9.3, content type
+        let mut other = tlv(
+            der::TAG_OID,
+            &[0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x09, 0x03],
+        );
+        other.extend(tlv(TAG_SET, &tlv(der::TAG_OID, &[0x2B, 0x06])));
+        let mut attrs = tlv(TAG_SEQUENCE, &other);
+        attrs.extend(signing_time_attr(&tlv(der::TAG_UTC_TIME, b"991231235959Z")));
+        assert_eq!(
+            parsed_signing_time(&signer_info(&attrs)).as_deref(),
+            Some("1999-12-31 23:59:59 UTC")
+        );
+    }
+
+    #[test]
+    fn signing_time_missing() {
+        assert_eq!(parsed_signing_time(&signer_info(&[])), None);
+        let without_attrs = tlv(TAG_SEQUENCE, &tlv(TAG_INTEGER, &[1]));
+        assert_eq!(parsed_signing_time(&without_attrs), None);
+    }
+}
@@ -0,0 +1,149 @@
+use pe_parse::PEHeader;
+use serde::Serialize;
+
+use crate::pe::{self, DIR_BOUND_IMPORT, imports::ImportedDll, read_cstr, read_u16, read_u32};
+
+const DESCRIPTOR_SIZE: usize = 8;
+
+/// Import descriptor stamp telling the loader to consult the bound import directory
+const NEW_STYLE_BINDING: u32 = 0xFFFF_FFFF;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BoundImport {
+    pub offset: usize,
+    pub time_date_stamp: u32,
+    pub module_name_offset: u16,
+    pub module_name: String,
+    pub forwarders: Vec<BoundForwarderRef>,
+    pub status: BindingStatus,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BoundForwarderRef {
+    pub offset: usize,
+    pub time_date_stamp: u32,
+    pub module_name_offset: u16,
+    pub module_name: String,
+    pub reserved: u16,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum BindingStatus {
+    Ok,
+    /// The bound module is not in the import directory
+    NotImported,
+    /// The import descriptor carries an old style stamp that differs from the binding
+    StampMismatch,
+    /// The import descriptor is not marked as bound
+    Unbound,
+}
+
+impl BindingStatus {
+    pub fn description(&self) -> &'static str {
+        match self {
+            BindingStatus::Ok => "OK",
+            BindingStatus::NotImported => "Stale: module not imported",
+            BindingStatus::StampMismatch => "Stale: TimeDateStamp mismatch",
+            BindingStatus::Unbound => "Stale: import descriptor not bound",
+        }
+    }
+}
+
+/// Compares a binding against the import descriptor for the same module
+fn binding_status(
+    module_name: &str,
+    time_date_stamp: u32,
+    imports: &[ImportedDll],
+) -> BindingStatus {
+    let Some(dll) = imports
+        .iter()
+        .find(|dll| dll.name.eq_ignore_ascii_case(module_name))
+    else {
+        return BindingStatus::NotImported;
+    };
+    match dll.time_date_stamp {
+        0 => BindingStatus::Unbound,
+        NEW_STYLE_BINDING => BindingStatus::Ok,
+        stamp if stamp == time_date_stamp => BindingStatus::Ok,
+        _ => BindingStatus::StampMismatch,
+    }
+}
+
+/// Import descriptors marked as bound that have no bound import descriptor
+pub fn missing_bindings<'a>(bound: &[BoundImport], imports: &'a [ImportedDll]) -> Vec<&'a str> {
+    imports
+        .iter()
+        .filter(|dll| dll.time_date_stamp == NEW_STYLE_BINDING)
+        .filter(|dll| {
+            !bound
+                .iter()
+                .any(|b| b.module_name.eq_ignore_ascii_case(&dll.name))
+        })
+        .map(|dll| dll.name.as_str())
+        .collect()
+}
+
+/// Parses the bound import directory, where module names are offsets from the directory start
+pub fn parse_bound_imports(
+    bytes: &[u8],
+    pe_header: &PEHeader,
+    imports: &[ImportedDll],
+) -> Vec<BoundImport> {
+    let Some(dir) = pe::data_directory(pe_header, DIR_BOUND_IMPORT) else {
+        return vec![];
+    };
+    // The directory normally lives in the headers, where RVAs equal file offsets
+    let Some(start) = pe::rva_to_offset(pe_header, dir.virtual_address) else {
+        return vec![];
+    };
+    let end = start.saturating_add(dir.size as usize).min(bytes.len());
+    let module_name = |name_offset: u16| {
+        read_cstr(bytes, start + name_offset as usize, 256)
+            .unwrap_or_else(|| format!("<invalid name 0x{:04X}>", name_offset))
+    };
+
+    let mut bound = vec![];
+    let mut offset = start;
+    while offset + DESCRIPTOR_SIZE <= end {
+        let (Some(time_date_stamp), Some(module_name_offset), Some(forwarder_count)) = (
+            read_u32(bytes, offset),
+            read_u16(bytes, offset + 4),
+            read_u16(bytes, offset + 6),
+        ) else {
+            break;
+        };
+        // The descriptor array ends with a zeroed entry
+        if time_date_stamp == 0 && module_name_offset == 0 {
+            break;
+        }
+
+        // Forwarder refs directly follow their descriptor and share its layout
+        let forwarders = (1..=forwarder_count as usize)
+            .map_while(|i| {
+                let ref_offset = offset + i * DESCRIPTOR_SIZE;
+                let name_offset = read_u16(bytes, ref_offset + 4)?;
+                Some(BoundForwarderRef {
+                    offset: ref_offset,
+                    time_date_stamp: read_u32(bytes, ref_offset)?,
+                    module_name_offset: name_offset,
+                    module_name: module_name(name_offset),
+                    reserved: read_u16(bytes, ref_offset + 6)?,
+                })
+            })
+            .collect();
+
+        let name = module_name(module_name_offset);
+        bound.push(BoundImport {
+            offset,
+            time_date_stamp,
+            module_name_offset,
+            status: binding_status(&name, time_date_stamp, imports),
+            module_name: name,
+            forwarders,
+        });
+
+        offset += (1 + forwarder_count as usize) * DESCRIPTOR_SIZE;
+    }
+
+    bound
+}
@@ -0,0 +1,816 @@
+use pe_parse::PEHeader;
+use serde::Serialize;
+
+use crate::pe::{self, DIR_COM_DESCRIPTOR, debug, read_cstr, read_u16, read_u32, read_u64};
+
+const METADATA_SIGNATURE: u32 = 0x424A_5342;
+
+/// Sentinel for unused slots in coded index target lists
+const UNUSED: u8 = 0xFF;
+
+pub const COMIMAGE_FLAGS: [(u32, &str); 6] = [
+    (0x0000_0001, "ILONLY"),
+    (0x0000_0002, "32BITREQUIRED"),
+    (0x0000_0004, "IL_LIBRARY"),
+    (0x0000_0008, "STRONGNAMESIGNED"),
+    (0x0000_0010, "NATIVE_ENTRYPOINT"),
+    (0x0002_0000, "32BITPREFERRED"),
+];
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ClrInfo {
+    pub header: Cor20Header,
+    pub metadata: Option<Metadata>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Cor20Header {
+    pub offset: usize,
+    pub cb: u32,
+    pub major_runtime_version: u16,
+    pub minor_runtime_version: u16,
+    pub metadata_rva: u32,
+    pub metadata_size: u32,
+    pub flags: u32,
+    pub entry_point: u32,
+    pub resources_rva: u32,
+    pub resources_size: u32,
+    pub strong_name_rva: u32,
+    pub strong_name_size: u32,
+    pub vtable_fixups_rva: u32,
+    pub vtable_fixups_size: u32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Metadata {
+    pub offset: usize,
+    pub major_version: u16,
+    pub minor_version: u16,
+    pub version: String,
+    pub flags: u16,
+    pub streams: Vec<StreamHeader>,
+    /// File offset of the `#~` (or `#-`) stream
+    pub tables_offset: Option<usize>,
+    pub tables_version: Option<(u8, u8)>,
+    pub heap_sizes: u8,
+    pub valid: u64,
+    pub sorted: u64,
+    pub tables: Vec<MetadataTable>,
+    pub user_strings: Vec<(u32, String)>,
+    pub guids: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct StreamHeader {
+    pub name: String,
+    pub offset: u32,
+    pub size: u32,
+}
+
+/// A metadata table with every cell already resolved against the heaps
+#[derive(Debug, Clone, Serialize)]
+pub struct MetadataTable {
+    pub id: u8,
+    pub name: &'static str,
+    pub columns: Vec<&'static str>,
+    pub rows: Vec<Vec<String>>,
+}
+
+pub fn flag_names(flags: u32) -> Vec<&'static str> {
+    COMIMAGE_FLAGS
+        .iter()
+        .filter(|(bit, _)| flags & bit != 0)
+        .map(|(_, name)| *name)
+        .collect()
+}
+
+#[derive(Clone, Copy)]
+enum Col {
+    U16,
+    U32,
+    Str,
+    Guid,
+    Blob,
+    Index(u8),
+    Coded(&'static [u8]),
+}
+
+use Col::*;
+
+const TYPE_DEF_OR_REF: &[u8] = &[0x02, 0x01, 0x1B];
+const HAS_CONSTANT: &[u8] = &[0x04, 0x08, 0x17];
+const HAS_CUSTOM_ATTRIBUTE: &[u8] = &[
+    0x06, 0x04, 0x01, 0x02, 0x08, 0x09, 0x0A, 0x00, 0x0E, 0x17, 0x14, 0x11, 0x1A, 0x1B, 0x20, 0x23,
+    0x26, 0x27, 0x28, 0x2A, 0x2C, 0x2B,
+];
+const HAS_FIELD_MARSHAL: &[u8] = &[0x04, 0x08];
+const HAS_DECL_SECURITY: &[u8] = &[0x02, 0x06, 0x20];
+const MEMBER_REF_PARENT: &[u8] = &[0x02, 0x01, 0x1A, 0x06, 0x1B];
+const HAS_SEMANTICS: &[u8] = &[0x14, 0x17];
+const METHOD_DEF_OR_REF: &[u8] = &[0x06, 0x0A];
+const MEMBER_FORWARDED: &[u8] = &[0x04, 0x06];
+const IMPLEMENTATION: &[u8] = &[0x26, 0x23, 0x27];
+const CUSTOM_ATTRIBUTE_TYPE: &[u8] = &[UNUSED, UNUSED, 0x06, 0x0A, UNUSED];
+const RESOLUTION_SCOPE: &[u8] = &[0x00, 0x1A, 0x23, 0x01];
+const TYPE_OR_METHOD_DEF: &[u8] = &[0x02, 0x06];
+
+/// Table layouts from ECMA-335 II.22, indexed by table id
+const SCHEMAS: [(&str, &[(&str, Col)]); 0x2D] = [
+    (
+        "Module",
+        &[
+            ("Generation", U16),
+            ("Name", Str),
+            ("Mvid", Guid),
+            ("EncId", Guid),
+            ("EncBaseId", Guid),
+        ],
+    ),
+    (
+        "TypeRef",
+        &[
+            ("ResolutionScope", Coded(RESOLUTION_SCOPE)),
+            ("TypeName", Str),
+            ("TypeNamespace", Str),
+        ],
+    ),
+    (
+        "TypeDef",
+        &[
+            ("Flags", U32),
+            ("TypeName", Str),
+            ("TypeNamespace", Str),
+            ("Extends", Coded(TYPE_DEF_OR_REF)),
+            ("FieldList", Index(0x04)),
+            ("MethodList", Index(0x06)),
+        ],
+    ),
+    ("FieldPtr", &[("Field", Index(0x04))]),
+    (
+        "Field",
+        &[("Flags", U16), ("Name", Str), ("Signature", Blob)],
+    ),
+    ("MethodPtr", &[("Method", Index(0x06))]),
+    (
+        "MethodDef",
+        &[
+            ("RVA", U32),
+            ("ImplFlags", U16),
+            ("Flags", U16),
+            ("Name", Str),
+            ("Signature", Blob),
+            ("ParamList", Index(0x08)),
+        ],
+    ),
+    ("ParamPtr", &[("Param", Index(0x08))]),
+    ("Param", &[("Flags", U16), ("Sequence", U16), ("Name", Str)]),
+    (
+        "InterfaceImpl",
+        &[
+            ("Class", Index(0x02)),
+            ("Interface", Coded(TYPE_DEF_OR_REF)),
+        ],
+    ),
+    (
+        "MemberRef",
+        &[
+            ("Class", Coded(MEMBER_REF_PARENT)),
+            ("Name", Str),
+            ("Signature", Blob),
+        ],
+    ),
+    (
+        "Constant",
+        &[
+            ("Type", U16),
+            ("Parent", Coded(HAS_CONSTANT)),
+            ("Value", Blob),
+        ],
+    ),
+    (
+        "CustomAttribute",
+        &[
+            ("Parent", Coded(HAS_CUSTOM_ATTRIBUTE)),
+            ("Type", Coded(CUSTOM_ATTRIBUTE_TYPE)),
+            ("Value", Blob),
+        ],
+    ),
+    (
+        "FieldMarshal",
+        &[("Parent", Coded(HAS_FIELD_MARSHAL)), ("NativeType", Blob)],
+    ),
+    (
+        "DeclSecurity",
+        &[
+            ("Action", U16),
+            ("Parent", Coded(HAS_DECL_SECURITY)),
+            ("PermissionSet", Blob),
+        ],
+    ),
+    (
+        "ClassLayout",
+        &[
+            ("PackingSize", U16),
+            ("ClassSize", U32),
+            ("Parent", Index(0x02)),
+        ],
+    ),
+    ("FieldLayout", &[("Offset", U32), ("Field", Index(0x04))]),
+    ("StandAloneSig", &[("Signature", Blob)]),
+    (
+        "EventMap",
+        &[("Parent", Index(0x02)), ("EventList", Index(0x14))],
+    ),
+    ("EventPtr", &[("Event", Index(0x14))]),
+    (
+        "Event",
+        &[
+            ("EventFlags", U16),
+            ("Name", Str),
+            ("EventType", Coded(TYPE_DEF_OR_REF)),
+        ],
+    ),
+    (
+        "PropertyMap",
+        &[("Parent", Index(0x02)), ("PropertyList", Index(0x17))],
+    ),
+    ("PropertyPtr", &[("Property", Index(0x17))]),
+    ("Property", &[("Flags", U16), ("Name", Str), ("Type", Blob)]),
+    (
+        "MethodSemantics",
+        &[
+            ("Semantics", U16),
+            ("Method", Index(0x06)),
+            ("Association", Coded(HAS_SEMANTICS)),
+        ],
+    ),
+    (
+        "MethodImpl",
+        &[
+            ("Class", Index(0x02)),
+            ("MethodBody", Coded(METHOD_DEF_OR_REF)),
+            ("MethodDeclaration", Coded(METHOD_DEF_OR_REF)),
+        ],
+    ),
+    ("ModuleRef", &[("Name", Str)]),
+    ("TypeSpec", &[("Signature", Blob)]),
+    (
+        "ImplMap",
+        &[
+            ("MappingFlags", U16),
+            ("MemberForwarded", Coded(MEMBER_FORWARDED)),
+            ("ImportName", Str),
+            ("ImportScope", Index(0x1A)),
+        ],
+    ),
+    ("FieldRVA", &[("RVA", U32), ("Field", Index(0x04))]),
+    ("EncLog", &[("Token", U32), ("FuncCode", U32)]),
+    ("EncMap", &[("Token", U32)]),
+    (
+        "Assembly",
+        &[
+            ("HashAlgId", U32),
+            ("MajorVersion", U16),
+            ("MinorVersion", U16),
+            ("BuildNumber", U16),
+            ("RevisionNumber", U16),
+            ("Flags", U32),
+            ("PublicKey", Blob),
+            ("Name", Str),
+            ("Culture", Str),
+        ],
+    ),
+    ("AssemblyProcessor", &[("Processor", U32)]),
+    (
+        "AssemblyOS",
+        &[
+            ("OSPlatformID", U32),
+            ("OSMajorVersion", U32),
+            ("OSMinorVersion", U32),
+        ],
+    ),
+    (
+        "AssemblyRef",
+        &[
+            ("MajorVersion", U16),
+            ("MinorVersion", U16),
+            ("BuildNumber", U16),
+            ("RevisionNumber", U16),
+            ("Flags", U32),
+            ("PublicKeyOrToken", Blob),
+            ("Name", Str),
+            ("Culture", Str),
+            ("HashValue", Blob),
+        ],
+    ),
+    (
+        "AssemblyRefProcessor",
+        &[("Processor", U32), ("AssemblyRef", Index(0x23))],
+    ),
+    (
+        "AssemblyRefOS",
+        &[
+            ("OSPlatformID", U32),
+            ("OSMajorVersion", U32),
+            ("OSMinorVersion", U32),
+            ("AssemblyRef", Index(0x23)),
+        ],
+    ),
+    (
+        "File",
+        &[("Flags", U32), ("Name", Str), ("HashValue", Blob)],
+    ),
+    (
+        "ExportedType",
+        &[
+            ("Flags", U32),
+            ("TypeDefId", U32),
+            ("TypeName", Str),
+            ("TypeNamespace", Str),
+            ("Implementation", Coded(IMPLEMENTATION)),
+        ],
+    ),
+    (
+        "ManifestResource",
+        &[
+            ("Offset", U32),
+            ("Flags", U32),
+            ("Name", Str),
+            ("Implementation", Coded(IMPLEMENTATION)),
+        ],
+    ),
+    (
+        "NestedClass",
+        &[
+            ("NestedClass", Index(0x02)),
+            ("EnclosingClass", Index(0x02)),
+        ],
+    ),
+    (
+        "GenericParam",
+        &[
+            ("Number", U16),
+            ("Flags", U16),
+            ("Owner", Coded(TYPE_OR_METHOD_DEF)),
+            ("Name", Str),
+        ],
+    ),
+    (
+        "MethodSpec",
+        &[
+            ("Method", Coded(METHOD_DEF_OR_REF)),
+            ("Instantiation", Blob),
+        ],
+    ),
+    (
+        "GenericParamConstraint",
+        &[
+            ("Owner", Index(0x2A)),
+            ("Constraint", Coded(TYPE_DEF_OR_REF)),
+        ],
+    ),
+];
+
+pub fn table_name(id: u8) -> &'static str {
+    SCHEMAS
+        .get(id as usize)
+        .map(|(name, _)| *name)
+        .unwrap_or("?")
+}
+
+/// Number of tag bits needed to address `count` tables
+fn tag_bits(count: usize) -> u32 {
+    usize::BITS - (count - 1).leading_zeros()
+}
+
+/// Reads an ECMA-335 compressed unsigned integer, returning the value and its size
+fn read_compressed(bytes: &[u8], offset: usize) -> Option<(usize, usize)> {
+    let b0 = *bytes.get(offset)? as usize;
+    if b0 & 0x80 == 0 {
+        Some((b0, 1))
+    } else if b0 & 0xC0 == 0x80 {
+        Some((((b0 & 0x3F) << 8) | *bytes.get(offset + 1)? as usize, 2))
+    } else if b0 & 0xE0 == 0xC0 {
+        let rest = bytes.get(offset + 1..offset + 4)?;
+        let value = ((b0 & 0x1F) << 24)
+            | ((rest[0] as usize) << 16)
+            | ((rest[1] as usize) << 8)
+            | rest[2] as usize;
+        Some((value, 4))
+    } else {
+        None
+    }
+}
+
+struct Heaps<'a> {
+    strings: &'a [u8],
+    guid: &'a [u8],
+    blob: &'a [u8],
+    heap_sizes: u8,
+    row_counts: [u32; 64],
+}
+
+impl Heaps<'_> {
+    fn col_size(&self, col: Col) -> usize {
+        let wide_heap = |bit: u8| if self.heap_sizes & bit != 0 { 4 } else { 2 };
+        match col {
+            U16 => 2,
+            U32 => 4,
+            Str => wide_heap(0x01),
+            Guid => wide_heap(0x02),
+            Blob => wide_heap(0x04),
+            Index(table) => {
+                if self.row_counts[table as usize] > 0xFFFF {
+                    4
+                } else {
+                    2
+                }
+            }
+            Coded(tables) => {
+                let max_rows = tables
+                    .iter()
+                    .filter(|table| **table != UNUSED)
+                    .map(|table| self.row_counts[*table as usize])
+                    .max()
+                    .unwrap_or(0);
+                if max_rows >= 1 << (16 - tag_bits(tables.len())) {
+                    4
+                } else {
+                    2
+                }
+            }
+        }
+    }
+
+    fn string(&self, index: u32) -> String {
+        read_cstr(self.strings, index as usize, 1024).unwrap_or_default()
+    }
+
+    fn guid(&self, index: u32) -> String {
+        // GUID heap indexes are 1 based
+        if index == 0 {
+            return String::new();
+        }
+        let start = (index as usize - 1) * 16;
+        self.guid
+            .get(start..)
+            .and_then(debug::format_guid)
+            .unwrap_or_default()
+    }
+
+    fn blob(&self, index: u32) -> String {
+        let Some((len, header)) = read_compressed(self.blob, index as usize) else {
+            return String::new();
+        };
+        let start = index as usize + header;
+        let Some(data) = self.blob.get(start..start + len) else {
+            return format!("<invalid blob 0x{:X}>", index);
+        };
+        let preview: String = data.iter().take(16).map(|b| format!("{:02X}", b)).collect();
+        let ellipsis = if len > 16 { "..." } else { "" };
+        format!("{}{} ({} bytes)", preview, ellipsis, len)
+    }
+
+    fn render(&self, col: Col, value: u32) -> String {
+        match col {
+            U16 => format!("0x{:04X}", value),
+            U32 => format!("0x{:08X}", value),
+            Str => self.string(value),
+            Guid => self.guid(value),
+            Blob => self.blob(value),
+            Index(table) => format!("{}[{}]", table_name(table), value),
+            Coded(tables) => {
+                let bits = tag_bits(tables.len());
+                let tag = (value & ((1 << bits) - 1)) as usize;
+                let row = value >> bits;
+                match tables.get(tag) {
+                    Some(table) if *table != UNUSED && row != 0 => {
+                        format!("{}[{}]", table_name(*table), row)
+                    }
+                    _ => format!("null (0x{:X})", value),
+                }
+            }
+        }
+    }
+}
+
+fn parse_header(bytes: &[u8], offset: usize) -> Option<Cor20Header> {
+    let u32_at = |rel: usize| read_u32(bytes, offset + rel);
+    Some(Cor20Header {
+        offset,
+        cb: u32_at(0)?,
+        major_runtime_version: read_u16(bytes, offset + 4)?,
+        minor_runtime_version: read_u16(bytes, offset + 6)?,
+        metadata_rva: u32_at(8)?,
+        metadata_size: u32_at(12)?,
+        flags: u32_at(16)?,
+        entry_point: u32_at(20)?,
+        resources_rva: u32_at(24)?,
+        resources_size: u32_at(28)?,
+        strong_name_rva: u32_at(32)?,
+        strong_name_size: u32_at(36)?,
+        vtable_fixups_rva: u32_at(48)?,
+        vtable_fixups_size: u32_at(52)?,
+    })
+}
+
+/// Walks the #US heap, where each entry is a UTF-16LE blob with a trailing flag byte
+fn parse_user_strings(us: &[u8]) -> Vec<(u32, String)> {
+    let mut strings = vec![];
+    let mut offset = 1;
+    while let Some((len, header)) = read_compressed(us, offset) {
+        let start = offset + header;
+        let Some(data) = us.get(start..start + len) else {
+            break;
+        };
+        if len > 1 {
+            let units: Vec<u16> = data[..len - 1]
+                .chunks_exact(2)
+                .map(|c| u16::from_le_bytes([c[0], c[1]]))
+                .collect();
+            strings.push((offset as u32, String::from_utf16_lossy(&units)));
+        }
+        offset = start + len;
+    }
+    strings
+}
+
+/// Decodes the `#~` (or uncompressed `#-`) table stream
+fn parse_tables(
+    stream: &[u8],
+    strings: &[u8],
+    guid: &[u8],
+    blob: &[u8],
+    metadata: &mut Metadata,
+) -> Option<()> {
+    metadata.tables_version = Some((*stream.get(4)?, *stream.get(5)?));
+    let heap_sizes = *stream.get(6)?;
+    let valid = read_u64(stream, 8)?;
+    metadata.heap_sizes = heap_sizes;
+    metadata.valid = valid;
+    metadata.sorted = read_u64(stream, 16)?;
+
+    let mut row_counts = [0u32; 64];
+    let mut offset = 24;
+    for (id, count) in row_counts.iter_mut().enumerate() {
+        if valid & (1 << id) != 0 {
+            *count = read_u32(stream, offset)?;
+            offset += 4;
+        }
+    }
+    // Some obfuscators and ENC images carry four extra bytes here
+    if heap_sizes & 0x40 != 0 {
+        offset += 4;
+    }
+
+    let heaps = Heaps {
+        strings,
+        guid,
+        blob,
+        heap_sizes,
+        row_counts,
+    };
+    for (id, (name, schema)) in SCHEMAS.iter().enumerate() {
+        let count = row_counts[id] as usize;
+        if count == 0 {
+            continue;
+        }
+        let row_size: usize = schema.iter().map(|(_, col)| heaps.col_size(*col)).sum();
+        let rows = (0..count)
+            .map_while(|row| {
+                let mut cell = offset + row * row_size;
+                let mut values = vec![format!("0x{:02X}{:06X}", id, row + 1)];
+                for (_, col) in schema.iter() {
+                    let value = match heaps.col_size(*col) {
+                        2 => read_u16(stream, cell)? as u32,
+                        _ => read_u32(stream, cell)?,
+                    };
+                    values.push(heaps.render(*col, value));
+                    cell += heaps.col_size(*col);
+                }
+                Some(values)
+            })
+            .collect();
+
+        let mut columns = vec!["Token"];
+        columns.extend(schema.iter().map(|(col_name, _)| *col_name));
+        metadata.tables.push(MetadataTable {
+            id: id as u8,
+            name,
+            columns,
+            rows,
+        });
+        offset += count * row_size;
+    }
+
+    // Tables past GenericParamConstraint have no known layout, so nothing follows them
+    Some(())
+}
+
+fn parse_metadata(bytes: &[u8], pe_header: &PEHeader, header: &Cor20Header) -> Option<Metadata> {
+    let offset = pe::rva_to_offset(pe_header, header.metadata_rva)?;
+    let end = offset
+        .saturating_add(header.metadata_size as usize)
+        .min(bytes.len());
+    let root = bytes.get(offset..end)?;
+    if read_u32(root, 0)? != METADATA_SIGNATURE {
+        return None;
+    }
+
+    let version_len = read_u32(root, 12)? as usize;
+    let version = read_cstr(root, 16, version_len).unwrap_or_else(|| {
+        String::from_utf8_lossy(root.get(16..16 + version_len).unwrap_or_default()).to_string()
+    });
+    let flags_offset = 16 + version_len;
+    let stream_count = read_u16(root, flags_offset + 2)?;
+
+    let mut streams = vec![];
+    let mut cursor = flags_offset + 4;
+    for _ in 0..stream_count {
+        let (Some(stream_offset), Some(size)) =
+            (read_u32(root, cursor), read_u32(root, cursor + 4))
+        else {
+            break;
+        };
+        let name = read_cstr(root, cursor + 8, 32).unwrap_or_default();
+        // Names are NUL terminated and padded to a four byte boundary
+        cursor += 8 + (name.len() + 1).next_multiple_of(4);
+        streams.push(StreamHeader {
+            name,
+            offset: stream_offset,
+            size,
+        });
+    }
+
+    let stream = |name: &str| -> &[u8] {
+        streams
+            .iter()
+            .find(|stream| stream.name == name)
+            .and_then(|stream| {
+                let start = stream.offset as usize;
+                root.get(start..start.saturating_add(stream.size as usize).min(root.len()))
+            })
+            .unwrap_or_default()
+    };
+    let strings = stream("#Strings");
+    let guid = stream("#GUID");
+    let blob = stream("#Blob");
+    let user_strings = parse_user_strings(stream("#US"));
+    let guids = guid
+        .chunks_exact(16)
+        .filter_map(debug::format_guid)
+        .collect();
+    let tables_stream = match stream("#~") {
+        [] => stream("#-"),
+        tables => tables,
+    };
+    let tables_offset = streams
+        .iter()
+        .find(|stream| stream.name == "#~")
+        .or_else(|| streams.iter().find(|stream| stream.name == "#-"))
+        .map(|stream| offset + stream.offset as usize);
+
+    let mut metadata = Metadata {
+        offset,
+        major_version: read_u16(root, 4)?,
+        minor_version: read_u16(root, 6)?,
+        version,
+        flags: read_u16(root, flags_offset)?,
+        streams: streams.clone(),
+        tables_offset,
+        tables_version: None,
+        heap_sizes: 0,
+        valid: 0,
+        sorted: 0,
+        tables: vec![],
+        user_strings,
+        guids,
+    };
+    parse_tables(tables_stream, strings, guid, blob, &mut metadata);
+
+    Some(metadata)
+}
+
+/// Parses the COM descriptor directory of managed images
+pub fn parse_clr(bytes: &[u8], pe_header: &PEHeader) -> Option<ClrInfo> {
+    let dir = pe::data_directory(pe_header, DIR_COM_DESCRIPTOR)?;
+    let offset = pe::rva_to_offset(pe_header, dir.virtual_address)?;
+    let header = parse_header(bytes, offset)?;
+    let metadata = parse_metadata(bytes, pe_header, &header);
+    Some(ClrInfo { header, metadata })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn heaps(heap_sizes: u8, counts: &[(u8, u32)]) -> Heaps<'static> {
+        let mut row_counts = [0; 64];
+        for (table, count) in counts {
+            row_counts[*table as usize] = *count;
+        }
+        Heaps {
+            strings: &[],
+            guid: &[],
+            blob: &[],
+            heap_sizes,
+            row_counts,
+        }
+    }
+
+    fn row_size(heaps: &Heaps, table: &str) -> usize {
+        let (_, schema) = SCHEMAS.iter().find(|(name, _)| *name == table).unwrap();
+        schema.iter().map(|(_, col)| heaps.col_size(*col)).sum()
+    }
+
+    #[test]
+    fn compressed_one_byte() {
+        assert_eq!(read_compressed(&[0x03], 0), Some((0x03, 1)));
+        assert_eq!(read_compressed(&[0x00, 0x7F], 1), Some((0x7F, 1)));
+    }
+
+    #[test]
+    fn compressed_two_bytes() {
+        assert_eq!(read_compressed(&[0x80, 0x80], 0), Some((0x80, 2)));
+        assert_eq!(read_compressed(&[0xAE, 0x57], 0), Some((0x2E57, 2)));
+        assert_eq!(read_compressed(&[0xBF, 0xFF], 0), Some((0x3FFF, 2)));
+        assert_eq!(read_compressed(&[0x80], 0), None);
+    }
+
+    #[test]
+    fn compressed_four_bytes() {
+        assert_eq!(
+            read_compressed(&[0xC0, 0x00, 0x40, 0x00], 0),
+            Some((0x4000, 4))
+        );
+        assert_eq!(
+            read_compressed(&[0xDF, 0xFF, 0xFF, 0xFF], 0),
+            Some((0x1FFF_FFFF, 4))
+        );
+        assert_eq!(read_compressed(&[0xC0, 0x00, 0x40], 0), None);
+        // 111xxxxx is not a valid length prefix
+        assert_eq!(read_compressed(&[0xE0, 0x00, 0x00, 0x00], 0), None);
+    }
+
+    #[test]
+    fn tag_bits_per_table_count() {
+        assert_eq!(tag_bits(HAS_SEMANTICS.len()), 1);
+        assert_eq!(tag_bits(TYPE_DEF_OR_REF.len()), 2);
+        assert_eq!(tag_bits(RESOLUTION_SCOPE.len()), 2);
+        assert_eq!(tag_bits(MEMBER_REF_PARENT.len()), 3);
+        assert_eq!(tag_bits(HAS_CUSTOM_ATTRIBUTE.len()), 5);
+    }
+
+    #[test]
+    fn coded_index_widens_at_row_threshold() {
+        // TypeDefOrRef has 2 tag bits, leaving 14 bits for the row in a 2 byte index
+        let col = Coded(TYPE_DEF_OR_REF);
+        assert_eq!(heaps(0, &[(0x01, 0x3FFF)]).col_size(col), 2);
+        assert_eq!(heaps(0, &[(0x01, 0x4000)]).col_size(col), 4);
+        assert_eq!(heaps(0, &[(0x1B, 0x4000), (0x02, 1)]).col_size(col), 4);
+
+        // HasSemantics has 1 tag bit
+        let col = Coded(HAS_SEMANTICS);
+        assert_eq!(heaps(0, &[(0x17, 0x7FFF)]).col_size(col), 2);
+        assert_eq!(heaps(0, &[(0x17, 0x8000)]).col_size(col), 4);
+
+        // Unused tags are not tables
+        let col = Coded(CUSTOM_ATTRIBUTE_TYPE);
+        assert_eq!(heaps(0, &[(UNUSED & 0x3F, 0x10000)]).col_size(col), 2);
+        assert_eq!(heaps(0, &[(0x06, 0x2000)]).col_size(col), 4);
+    }
+
+    #[test]
+    fn simple_index_and_heap_widths() {
+        assert_eq!(heaps(0, &[(0x04, 0xFFFF)]).col_size(Index(0x04)), 2);
+        assert_eq!(heaps(0, &[(0x04, 0x10000)]).col_size(Index(0x04)), 4);
+        let wide = heaps(0x07, &[]);
+        assert_eq!(wide.col_size(Str), 4);
+        assert_eq!(wide.col_size(Guid), 4);
+        assert_eq!(wide.col_size(Blob), 4);
+        assert_eq!(heaps(0x02, &[]).col_size(Str), 2);
+    }
+
+    #[test]
+    fn row_sizes() {
+        assert_eq!(row_size(&heaps(0, &[]), "TypeDef"), 14);
+        assert_eq!(row_size(&heaps(0x01, &[]), "TypeDef"), 18);
+        assert_eq!(row_size(&heaps(0, &[(0x01, 0x4000)]), "TypeDef"), 16);
+        assert_eq!(row_size(&heaps(0, &[(0x06, 0x10000)]), "TypeDef"), 16);
+        assert_eq!(row_size(&heaps(0, &[]), "Module"), 10);
+    }
+
+    #[test]
+    fn coded_index_rendering() {
+        let heaps = heaps(0, &[]);
+        assert_eq!(
+            heaps.render(Coded(TYPE_DEF_OR_REF), (5 << 2) | 1),
+            "TypeRef[5]"
+        );
+        assert_eq!(heaps.render(Coded(TYPE_DEF_OR_REF), 3), "null (0x3)");
+        assert_eq!(
+            heaps.render(Coded(CUSTOM_ATTRIBUTE_TYPE), 1 << 3),
+            "null (0x8)"
+        );
+    }
+}
@@ -0,0 +1,210 @@
+use pe_parse::PEHeader;
+use serde::Serialize;
+
+use crate::pe::{self, DIR_DEBUG, read_cstr, read_u16, read_u32};
+
+const ENTRY_SIZE: usize = 28;
+
+pub const IMAGE_DEBUG_TYPE_CODEVIEW: u32 = 2;
+pub const IMAGE_DEBUG_TYPE_VC_FEATURE: u32 = 12;
+pub const IMAGE_DEBUG_TYPE_POGO: u32 = 13;
+pub const IMAGE_DEBUG_TYPE_REPRO: u32 = 16;
+pub const IMAGE_DEBUG_TYPE_EX_DLLCHARACTERISTICS: u32 = 20;
+
+const EX_DLL_CHARACTERISTICS: [(u32, &str); 6] = [
+    (0x01, "CET_COMPAT"),
+    (0x02, "CET_COMPAT_STRICT_MODE"),
+    (0x04, "CET_SET_CONTEXT_IP_VALIDATION_RELAXED_MODE"),
+    (0x08, "CET_DYNAMIC_APIS_ALLOW_IN_PROC"),
+    (0x40, "FORWARD_CFI_COMPAT"),
+    (0x80, "HOTPATCH_COMPATIBLE"),
+];
+
+pub fn type_name(kind: u32) -> &'static str {
+    match kind {
+        0 => "UNKNOWN",
+        1 => "COFF",
+        2 => "CODEVIEW",
+        3 => "FPO",
+        4 => "MISC",
+        5 => "EXCEPTION",
+        6 => "FIXUP",
+        7 => "OMAP_TO_SRC",
+        8 => "OMAP_FROM_SRC",
+        9 => "BORLAND",
+        10 => "RESERVED10",
+        11 => "CLSID",
+        12 => "VC_FEATURE",
+        13 => "POGO",
+        14 => "ILTCG",
+        15 => "MPX",
+        16 => "REPRO",
+        17 => "EMBEDDED_PORTABLE_PDB",
+        19 => "PDBCHECKSUM",
+        20 => "EX_DLLCHARACTERISTICS",
+        _ => "UNKNOWN",
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DebugEntry {
+    pub offset: usize,
+    pub characteristics: u32,
+    pub time_date_stamp: u32,
+    pub major_version: u16,
+    pub minor_version: u16,
+    pub kind: u32,
+    pub size_of_data: u32,
+    pub address_of_raw_data: u32,
+    pub pointer_to_raw_data: u32,
+    pub info: DebugInfo,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub enum DebugInfo {
+    CodeViewRsds {
+        guid: String,
+        age: u32,
+        pdb_path: String,
+    },
+    CodeViewNb10 {
+        offset: u32,
+        signature: u32,
+        age: u32,
+        pdb_path: String,
+    },
+    Pogo {
+        signature: String,
+        entries: Vec<(u32, u32, String)>,
+    },
+    Repro {
+        hash: String,
+    },
+    VcFeature {
+        pre_vc11: u32,
+        c_cpp: u32,
+        gs: u32,
+        sdl: u32,
+        guard_n: u32,
+    },
+    ExDllCharacteristics {
+        flags: u32,
+        names: Vec<String>,
+    },
+    None,
+}
+
+pub fn format_guid(raw: &[u8]) -> Option<String> {
+    let raw: &[u8; 16] = raw.get(..16)?.try_into().ok()?;
+    let d1 = u32::from_le_bytes(raw[0..4].try_into().ok()?);
+    let d2 = u16::from_le_bytes([raw[4], raw[5]]);
+    let d3 = u16::from_le_bytes([raw[6], raw[7]]);
+    let tail: String = raw[8..].iter().map(|b| format!("{:02X}", b)).collect();
+    Some(format!(
+        "{{{:08X}-{:04X}-{:04X}-{}-{}}}",
+        d1,
+        d2,
+        d3,
+        &tail[..4],
+        &tail[4..]
+    ))
+}
+
+fn decode_info(kind: u32, data: &[u8]) -> Option<DebugInfo> {
+    match kind {
+        IMAGE_DEBUG_TYPE_CODEVIEW if data.starts_with(b"RSDS") => Some(DebugInfo::CodeViewRsds {
+            guid: format_guid(data.get(4..20)?)?,
+            age: read_u32(data, 20)?,
+            pdb_path: read_cstr(data, 24, data.len()).unwrap_or_default(),
+        }),
+        IMAGE_DEBUG_TYPE_CODEVIEW if data.starts_with(b"NB10") => Some(DebugInfo::CodeViewNb10 {
+            offset: read_u32(data, 4)?,
+            signature: read_u32(data, 8)?,
+            age: read_u32(data, 12)?,
+            pdb_path: read_cstr(data, 16, data.len()).unwrap_or_default(),
+        }),
+        IMAGE_DEBUG_TYPE_POGO => {
+            let signature = String::from_utf8_lossy(data.get(..4)?)
+                .trim_end_matches('\0')
+                .to_string();
+            let mut entries = vec![];
+            let mut offset = 4;
+            while let (Some(rva), Some(size)) = (read_u32(data, offset), read_u32(data, offset + 4))
+            {
+                let Some(name) = read_cstr(data, offset + 8, data.len()) else {
+                    break;
+                };
+                // Names are NUL terminated and padded to a 4 byte boundary
+                offset = (offset + 8 + name.len() + 1 + 3) & !3;
+                entries.push((rva, size, name));
+            }
+            Some(DebugInfo::Pogo { signature, entries })
+        }
+        IMAGE_DEBUG_TYPE_REPRO => {
+            // Deterministic builds without a hash leave the entry empty
+            let hash = match read_u32(data, 0) {
+                Some(len) => data
+                    .get(4..4 + len as usize)?
+                    .iter()
+                    .map(|b| format!("{:02x}", b))
+                    .collect(),
+                None => String::new(),
+            };
+            Some(DebugInfo::Repro { hash })
+        }
+        IMAGE_DEBUG_TYPE_VC_FEATURE => Some(DebugInfo::VcFeature {
+            pre_vc11: read_u32(data, 0)?,
+            c_cpp: read_u32(data, 4)?,
+            gs: read_u32(data, 8)?,
+            sdl: read_u32(data, 12)?,
+            guard_n: read_u32(data, 16)?,
+        }),
+        IMAGE_DEBUG_TYPE_EX_DLLCHARACTERISTICS => {
+            let flags = read_u32(data, 0)?;
+            let names = EX_DLL_CHARACTERISTICS
+                .iter()
+                .filter(|(bit, _)| flags & bit != 0)
+                .map(|(_, name)| name.to_string())
+                .collect();
+            Some(DebugInfo::ExDllCharacteristics { flags, names })
+        }
+        _ => None,
+    }
+}
+
+pub fn parse_debug(bytes: &[u8], pe_header: &PEHeader) -> Vec<DebugEntry> {
+    let Some(dir) = pe::data_directory(pe_header, DIR_DEBUG) else {
+        return vec![];
+    };
+    let Some(start) = pe::rva_to_offset(pe_header, dir.virtual_address) else {
+        return vec![];
+    };
+
+    (0..dir.size as usize / ENTRY_SIZE)
+        .map_while(|i| {
+            let offset = start + i * ENTRY_SIZE;
+            let kind = read_u32(bytes, offset + 12)?;
+            let size_of_data = read_u32(bytes, offset + 16)?;
+            let pointer_to_raw_data = read_u32(bytes, offset + 24)?;
+
+            let data_start = pointer_to_raw_data as usize;
+            let info = bytes
+                .get(data_start..data_start.saturating_add(size_of_data as usize))
+                .and_then(|data| decode_info(kind, data))
+                .unwrap_or(DebugInfo::None);
+
+            Some(DebugEntry {
+                offset,
+                characteristics: read_u32(bytes, offset)?,
+                time_date_stamp: read_u32(bytes, offset + 4)?,
+                major_version: read_u16(bytes, offset + 8)?,
+                minor_version: read_u16(bytes, offset + 10)?,
+                kind,
+                size_of_data,
+                address_of_raw_data: read_u32(bytes, offset + 20)?,
+                pointer_to_raw_data,
+                info,
+            })
+        })
+        .collect()
+}
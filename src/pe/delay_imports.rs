@@ -0,0 +1,123 @@
+use pe_parse::PEHeader;
+use serde::Serialize;
+
+use crate::pe::{
+    self, DIR_DELAY_IMPORT,
+    imports::{self, ImportedFunction},
+    read_cstr, read_u32, read_u64,
+};
+
+const DESCRIPTOR_SIZE: usize = 32;
+
+/// Set when the descriptor holds RVAs, clear for the legacy VA based layout
+pub const DLATTR_RVA: u32 = 0x1;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DelayImportedDll {
+    pub name: String,
+    pub descriptor_offset: usize,
+    pub attributes: u32,
+    pub rva_based: bool,
+    pub dll_name_rva: u32,
+    pub module_handle_rva: u32,
+    pub iat_rva: u32,
+    pub int_rva: u32,
+    pub bound_iat_rva: u32,
+    pub unload_iat_rva: u32,
+    pub time_date_stamp: u32,
+    pub functions: Vec<DelayImportedFunction>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DelayImportedFunction {
+    pub function: ImportedFunction,
+    /// Initial IAT entry, usually pointing at the delay load helper thunk
+    pub iat_value: Option<u64>,
+    pub bound_value: Option<u64>,
+    pub unload_value: Option<u64>,
+}
+
+/// Reads entry `index` of a pointer sized table, if the table is present
+fn read_table_entry(bytes: &[u8], pe_header: &PEHeader, rva: u32, index: usize) -> Option<u64> {
+    if rva == 0 {
+        return None;
+    }
+    let offset = pe::rva_to_offset(pe_header, rva)?;
+    if pe::is_pe64(pe_header) {
+        read_u64(bytes, offset + index * 8)
+    } else {
+        read_u32(bytes, offset + index * 4).map(|v| v as u64)
+    }
+}
+
+pub fn parse_delay_imports(bytes: &[u8], pe_header: &PEHeader) -> Vec<DelayImportedDll> {
+    let Some(dir) = pe::data_directory(pe_header, DIR_DELAY_IMPORT) else {
+        return vec![];
+    };
+    let Some(mut offset) = pe::rva_to_offset(pe_header, dir.virtual_address) else {
+        return vec![];
+    };
+    let image_base = pe::image_base(pe_header);
+
+    let mut dlls = vec![];
+    while let Some(raw) = (0..8)
+        .map(|i| read_u32(bytes, offset + i * 4))
+        .collect::<Option<Vec<u32>>>()
+    {
+        // The descriptor array ends with a zeroed entry
+        if raw.iter().all(|field| *field == 0) {
+            break;
+        }
+
+        let attributes = raw[0];
+        let rva_based = attributes & DLATTR_RVA != 0;
+        // Legacy descriptors store VAs, which only fit 32 bit images
+        let to_rva = |value: u32| {
+            if rva_based || value == 0 {
+                value
+            } else {
+                value.wrapping_sub(image_base as u32)
+            }
+        };
+        let dll_name_rva = to_rva(raw[1]);
+        let iat_rva = to_rva(raw[3]);
+        let int_rva = to_rva(raw[4]);
+        let bound_iat_rva = to_rva(raw[5]);
+        let unload_iat_rva = to_rva(raw[6]);
+
+        let name = pe::rva_to_offset(pe_header, dll_name_rva)
+            .and_then(|name_offset| read_cstr(bytes, name_offset, 256))
+            .unwrap_or_else(|| format!("<invalid name 0x{:08X}>", dll_name_rva));
+
+        let name_base = if rva_based { 0 } else { image_base };
+        let functions = imports::parse_thunks(bytes, pe_header, int_rva, iat_rva, name_base)
+            .into_iter()
+            .enumerate()
+            .map(|(i, function)| DelayImportedFunction {
+                function,
+                iat_value: read_table_entry(bytes, pe_header, iat_rva, i),
+                bound_value: read_table_entry(bytes, pe_header, bound_iat_rva, i),
+                unload_value: read_table_entry(bytes, pe_header, unload_iat_rva, i),
+            })
+            .collect();
+
+        dlls.push(DelayImportedDll {
+            name,
+            descriptor_offset: offset,
+            attributes,
+            rva_based,
+            dll_name_rva,
+            module_handle_rva: to_rva(raw[2]),
+            iat_rva,
+            int_rva,
+            bound_iat_rva,
+            unload_iat_rva,
+            time_date_stamp: raw[7],
+            functions,
+        });
+
+        offset += DESCRIPTOR_SIZE;
+    }
+
+    dlls
+}
@@ -0,0 +1,244 @@
+pub const TAG_INTEGER: u8 = 0x02;
+pub const TAG_OCTET_STRING: u8 = 0x04;
+pub const TAG_OID: u8 = 0x06;
+pub const TAG_UTF8_STRING: u8 = 0x0C;
+pub const TAG_PRINTABLE_STRING: u8 = 0x13;
+pub const TAG_IA5_STRING: u8 = 0x16;
+pub const TAG_UTC_TIME: u8 = 0x17;
+pub const TAG_GENERALIZED_TIME: u8 = 0x18;
+pub const TAG_BMP_STRING: u8 = 0x1E;
+pub const TAG_SEQUENCE: u8 = 0x30;
+pub const TAG_SET: u8 = 0x31;
+
+/// A single DER element
+#[derive(Debug, Clone, Copy)]
+pub struct Tlv<'a> {
+    pub tag: u8,
+    pub content: &'a [u8],
+    pub raw: &'a [u8],
+}
+
+/// Parses one element from the front of `data`, returning it and the remainder
+pub fn parse(data: &[u8]) -> Option<(Tlv<'_>, &[u8])> {
+    let tag = *data.first()?;
+    let first = *data.get(1)?;
+    let (len, header_len) = if first & 0x80 == 0 {
+        (first as usize, 2)
+    } else {
+        let count = (first & 0x7F) as usize;
+        if count == 0 || count > 4 {
+            return None;
+        }
+        let len = data
+            .get(2..2 + count)?
+            .iter()
+            .fold(0usize, |acc, b| (acc << 8) | *b as usize);
+        (len, 2 + count)
+    };
+    let end = header_len.checked_add(len)?;
+    let content = data.get(header_len..end)?;
+    Some((
+        Tlv {
+            tag,
+            content,
+            raw: &data[..end],
+        },
+        &data[end..],
+    ))
+}
+
+impl<'a> Tlv<'a> {
+    pub fn children(&self) -> Vec<Tlv<'a>> {
+        let mut children = vec![];
+        let mut rest = self.content;
+        while let Some((child, tail)) = parse(rest) {
+            children.push(child);
+            rest = tail;
+        }
+        children
+    }
+
+    /// Context specific tag `[n]`, constructed or primitive
+    pub fn is_context(&self, n: u8) -> bool {
+        self.tag & 0xC0 == 0x80 && self.tag & 0x1F == n
+    }
+
+    pub fn oid(&self) -> Option<String> {
+        (self.tag == TAG_OID).then(|| decode_oid(self.content))
+    }
+
+    pub fn integer_hex(&self) -> String {
+        let content = match self.content {
+            [0, rest @ ..] if !rest.is_empty() => rest,
+            content => content,
+        };
+        content.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    pub fn string(&self) -> Option<String> {
+        match self.tag {
+            TAG_UTF8_STRING | TAG_PRINTABLE_STRING | TAG_IA5_STRING | 0x14 | 0x1A => {
+                Some(String::from_utf8_lossy(self.content).to_string())
+            }
+            TAG_BMP_STRING => {
+                let units: Vec<u16> = self
+                    .content
+                    .chunks_exact(2)
+                    .map(|c| u16::from_be_bytes([c[0], c[1]]))
+                    .collect();
+                Some(String::from_utf16_lossy(&units))
+            }
+            _ => None,
+        }
+    }
+
+    /// Formats UTCTime and GeneralizedTime as `YYYY-MM-DD HH:MM:SS UTC`
+    pub fn time(&self) -> Option<String> {
+        let text = std::str::from_utf8(self.content).ok()?;
+        let full = match self.tag {
+            TAG_UTC_TIME => {
+                let year: u32 = text.get(..2)?.parse().ok()?;
+                let century = if year >= 50 { "19" } else { "20" };
+                format!("{}{}", century, text)
+            }
+            TAG_GENERALIZED_TIME => text.to_string(),
+            _ => return None,
+        };
+        Some(format!(
+            "{}-{}-{} {}:{}:{} UTC",
+            full.get(0..4)?,
+            full.get(4..6)?,
+            full.get(6..8)?,
+            full.get(8..10)?,
+            full.get(10..12)?,
+            full.get(12..14).unwrap_or("00"),
+        ))
+    }
+}
+
+pub fn decode_oid(content: &[u8]) -> String {
+    let mut parts = vec![];
+    let mut value: u64 = 0;
+    for b in content {
+        value = (value << 7) | (b & 0x7F) as u64;
+        if b & 0x80 == 0 {
+            if parts.is_empty() {
+                let first = (value / 40).min(2);
+                parts.push(first.to_string());
+                parts.push((value - first * 40).to_string());
+            } else {
+                parts.push(value.to_string());
+            }
+            value = 0;
+        }
+    }
+    parts.join(".")
+}
+
+fn attribute_name(oid: &str) -> &str {
+    match oid {
+        "2.5.4.3" => "CN",
+        "2.5.4.6" => "C",
+        "2.5.4.7" => "L",
+        "2.5.4.8" => "ST",
+        "2.5.4.10" => "O",
+        "2.5.4.11" => "OU",
+        "1.2.840.113549.1.9.1" => "E",
+        other => other,
+    }
+}
+
+/// Renders an X.501 Name as a comma separated list of attributes
+pub fn format_name(name: &Tlv) -> String {
+    name.children()
+        .iter()
+        .flat_map(|rdn| rdn.children())
+        .filter_map(|attr| {
+            let parts = attr.children();
+            let oid = parts.first()?.oid()?;
+            let value = parts.get(1)?.string()?;
+            Some(format!("{}={}", attribute_name(&oid), value))
+        })
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn short_form_length() {
+        let (tlv, rest) = parse(&[TAG_OCTET_STRING, 2, 0xAA, 0xBB, 0xCC]).unwrap();
+        assert_eq!(tlv.tag, TAG_OCTET_STRING);
+        assert_eq!(tlv.content, &[0xAA, 0xBB]);
+        assert_eq!(tlv.raw, &[TAG_OCTET_STRING, 2, 0xAA, 0xBB]);
+        assert_eq!(rest, &[0xCC]);
+    }
+
+    #[test]
+    fn long_form_length() {
+        let mut data = vec![TAG_OCTET_STRING, 0x82, 0x01, 0x00];
+        data.extend(std::iter::repeat_n(0x5A, 0x100));
+        data.push(0xFF);
+        let (tlv, rest) = parse(&data).unwrap();
+        assert_eq!(tlv.content.len(), 0x100);
+        assert_eq!(tlv.raw.len(), 0x104);
+        assert_eq!(rest, &[0xFF]);
+
+        let (tlv, _) = parse(&[TAG_OCTET_STRING, 0x81, 0x01, 0x7F]).unwrap();
+        assert_eq!(tlv.content, &[0x7F]);
+    }
+
+    #[test]
+    fn truncated_input() {
+        assert!(parse(&[]).is_none());
+        assert!(parse(&[TAG_SEQUENCE]).is_none());
+        // Content shorter than the length
+        assert!(parse(&[TAG_OCTET_STRING, 3, 1, 2]).is_none());
+        // Long form length bytes cut off
+        assert!(parse(&[TAG_OCTET_STRING, 0x82, 0x01]).is_none());
+        assert!(parse(&[TAG_OCTET_STRING, 0x82, 0x01, 0x00, 0x00]).is_none());
+    }
+
+    #[test]
+    fn unsupported_length_forms() {
+        // Indefinite length is not DER
+        assert!(parse(&[TAG_SEQUENCE, 0x80, 0, 0]).is_none());
+        // Lengths wider than four bytes
+        assert!(parse(&[TAG_OCTET_STRING, 0x85, 0, 0, 0, 0, 1, 0]).is_none());
+    }
+
+    #[test]
+    fn children_stop_at_truncated_element() {
+        let data = [TAG_SEQUENCE, 6, TAG_INTEGER, 1, 5, TAG_INTEGER, 3, 1];
+        let (seq, _) = parse(&data).unwrap();
+        let children = seq.children();
+        assert_eq!(children.len(), 1);
+        assert_eq!(children[0].integer_hex(), "05");
+    }
+
+    #[test]
+    fn oid_decoding() {
+        let content = [0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x07, 0x02];
+        assert_eq!(decode_oid(&content), "1.2.840.113549.1.7.2");
+        assert_eq!(decode_oid(&[0x55, 0x04, 0x03]), "2.5.4.3");
+    }
+
+    #[test]
+    fn utc_time_century() {
+        let time = |text: &[u8]| {
+            let data = [&[TAG_UTC_TIME, text.len() as u8][..], text].concat();
+            parse(&data).and_then(|(tlv, _)| tlv.time())
+        };
+        assert_eq!(
+            time(b"491231235959Z").as_deref(),
+            Some("2049-12-31 23:59:59 UTC")
+        );
+        // Seconds are optional
+        assert_eq!(
+            time(b"5001010000Z").as_deref(),
+            Some("1950-01-01 00:00:00 UTC")
+        );
+    }
+}
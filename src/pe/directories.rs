@@ -0,0 +1,99 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use pe_parse::PEHeader;
+
+use crate::pe::{
+    authenticode::{self, WinCertificate},
+    bound_imports::{self, BoundImport},
+    clr::{self, ClrInfo},
+    debug::{self, DebugEntry},
+    delay_imports::{self, DelayImportedDll},
+    exception::{self, RuntimeFunction},
+    exports::{self, ExportDirectory},
+    imports::{self, ImportedDll},
+    load_config::{self, LoadConfig},
+    relocations::{self, RelocationBlock},
+    resources::{self, ResourceDirectory},
+    rich::{self, RichHeader},
+    sections,
+    tls::{self, TlsDirectory},
+};
+
+/// Everything the Info route shows beyond the headers, parsed by the loader off the UI thread
+#[derive(Default)]
+pub struct Directories {
+    pub imports: Vec<ImportedDll>,
+    pub delay_imports: Vec<DelayImportedDll>,
+    pub bound_imports: Vec<BoundImport>,
+    /// Imports marked as bound without a bound import descriptor
+    pub missing_bindings: Vec<String>,
+    pub exports: Option<ExportDirectory>,
+    pub resources: ResourceDirectory,
+    pub relocations: Vec<RelocationBlock>,
+    pub tls: Option<TlsDirectory>,
+    pub debug: Vec<DebugEntry>,
+    pub load_config: Option<LoadConfig>,
+    pub exceptions: Vec<RuntimeFunction>,
+    pub certificates: Vec<WinCertificate>,
+    pub clr: Option<ClrInfo>,
+    pub rich: Option<RichHeader>,
+    /// Entropy of each section's raw data, in section table order
+    pub section_entropy: Vec<f64>,
+}
+
+/// Parses every directory, giving up with `None` once `cancelled` is set.
+/// The flag is checked between directories, each parser bounds its own work.
+pub fn parse_directories(
+    bytes: &[u8],
+    pe_header: &PEHeader,
+    cancelled: &AtomicBool,
+) -> Option<Directories> {
+    let check = || (!cancelled.load(Ordering::Relaxed)).then_some(());
+
+    let imports = imports::parse_imports(bytes, pe_header);
+    check()?;
+    let bound_imports = bound_imports::parse_bound_imports(bytes, pe_header, &imports);
+    let missing_bindings = bound_imports::missing_bindings(&bound_imports, &imports)
+        .into_iter()
+        .map(|name| name.to_string())
+        .collect();
+    let delay_imports = delay_imports::parse_delay_imports(bytes, pe_header);
+    let exports = exports::parse_exports(bytes, pe_header);
+    check()?;
+    let resources = resources::parse_resources(bytes, pe_header);
+    check()?;
+    let relocations = relocations::parse_relocations(bytes, pe_header);
+    let tls = tls::parse_tls(bytes, pe_header);
+    let debug = debug::parse_debug(bytes, pe_header);
+    let load_config = load_config::parse_load_config(bytes, pe_header);
+    check()?;
+    let exceptions = exception::parse_exceptions(bytes, pe_header);
+    let certificates = authenticode::parse_certificates(bytes, pe_header);
+    check()?;
+    let clr = clr::parse_clr(bytes, pe_header);
+    let rich = rich::parse_rich(bytes, pe_header);
+    check()?;
+    let section_entropy = pe_header
+        .section_headers
+        .iter()
+        .map(|sct| sections::entropy(sections::raw_data(bytes, sct)))
+        .collect();
+
+    Some(Directories {
+        imports,
+        delay_imports,
+        bound_imports,
+        missing_bindings,
+        exports,
+        resources,
+        relocations,
+        tls,
+        debug,
+        load_config,
+        exceptions,
+        certificates,
+        clr,
+        rich,
+        section_entropy,
+    })
+}
@@ -0,0 +1,189 @@
+use pe_parse::PEHeader;
+use serde::Serialize;
+
+use crate::pe::{self, DIR_EXCEPTION, read_u16, read_u32};
+
+const RUNTIME_FUNCTION_SIZE: usize = 12;
+
+pub const UNW_FLAG_EHANDLER: u8 = 0x1;
+pub const UNW_FLAG_UHANDLER: u8 = 0x2;
+pub const UNW_FLAG_CHAININFO: u8 = 0x4;
+
+const REGISTERS: [&str; 16] = [
+    "RAX", "RCX", "RDX", "RBX", "RSP", "RBP", "RSI", "RDI", "R8", "R9", "R10", "R11", "R12", "R13",
+    "R14", "R15",
+];
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RuntimeFunction {
+    pub offset: usize,
+    pub begin_address: u32,
+    pub end_address: u32,
+    pub unwind_info_address: u32,
+    pub unwind_info: Option<UnwindInfo>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct UnwindInfo {
+    pub version: u8,
+    pub flags: u8,
+    pub size_of_prolog: u8,
+    pub frame_register: u8,
+    pub frame_offset: u8,
+    pub codes: Vec<UnwindCode>,
+    pub handler: Option<u32>,
+    pub chained: Option<(u32, u32, u32)>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct UnwindCode {
+    pub code_offset: u8,
+    pub op: u8,
+    pub description: String,
+}
+
+pub fn flag_names(flags: u8) -> Vec<&'static str> {
+    let mut names = vec![];
+    if flags & UNW_FLAG_EHANDLER != 0 {
+        names.push("EHANDLER");
+    }
+    if flags & UNW_FLAG_UHANDLER != 0 {
+        names.push("UHANDLER");
+    }
+    if flags & UNW_FLAG_CHAININFO != 0 {
+        names.push("CHAININFO");
+    }
+    names
+}
+
+pub fn register_name(reg: u8) -> &'static str {
+    REGISTERS.get(reg as usize).copied().unwrap_or("?")
+}
+
+/// Decodes the unwind code array, where some operations use extra slots
+fn decode_codes(slots: &[u16], version: u8) -> Vec<UnwindCode> {
+    let mut codes = vec![];
+    let mut i = 0;
+    while i < slots.len() {
+        let slot = slots[i];
+        let code_offset = (slot & 0xFF) as u8;
+        let op = ((slot >> 8) & 0x0F) as u8;
+        let info = (slot >> 12) as u8;
+        let next = |n: usize| slots.get(i + n).copied().unwrap_or(0) as u32;
+        let far = |n: usize| next(n) | (next(n + 1) << 16);
+
+        let (description, used) = match op {
+            0 => (format!("PUSH_NONVOL {}", register_name(info)), 1),
+            1 if info == 0 => (format!("ALLOC_LARGE 0x{:X}", next(1) * 8), 2),
+            1 => (format!("ALLOC_LARGE 0x{:X}", far(1)), 3),
+            2 => (format!("ALLOC_SMALL 0x{:X}", info as u32 * 8 + 8), 1),
+            3 => ("SET_FPREG".to_string(), 1),
+            4 => (
+                format!(
+                    "SAVE_NONVOL {} [rsp+0x{:X}]",
+                    register_name(info),
+                    next(1) * 8
+                ),
+                2,
+            ),
+            5 => (
+                format!(
+                    "SAVE_NONVOL_FAR {} [rsp+0x{:X}]",
+                    register_name(info),
+                    far(1)
+                ),
+                3,
+            ),
+            6 if version >= 2 => (format!("EPILOG size 0x{:X}", code_offset), 1),
+            6 => (format!("SAVE_XMM XMM{} [rsp+0x{:X}]", info, next(1) * 8), 2),
+            7 if version >= 2 => ("SPARE".to_string(), 1),
+            7 => (format!("SAVE_XMM_FAR XMM{} [rsp+0x{:X}]", info, far(1)), 3),
+            8 => (
+                format!("SAVE_XMM128 XMM{} [rsp+0x{:X}]", info, next(1) * 16),
+                2,
+            ),
+            9 => (
+                format!("SAVE_XMM128_FAR XMM{} [rsp+0x{:X}]", info, far(1)),
+                3,
+            ),
+            10 if info == 1 => ("PUSH_MACHFRAME with error code".to_string(), 1),
+            10 => ("PUSH_MACHFRAME".to_string(), 1),
+            _ => (format!("UNKNOWN op {}", op), 1),
+        };
+
+        codes.push(UnwindCode {
+            code_offset,
+            op,
+            description,
+        });
+        i += used;
+    }
+    codes
+}
+
+fn parse_unwind_info(bytes: &[u8], pe_header: &PEHeader, rva: u32) -> Option<UnwindInfo> {
+    let offset = pe::rva_to_offset(pe_header, rva)?;
+    let header = bytes.get(offset..offset + 4)?;
+    let version = header[0] & 0x07;
+    let flags = header[0] >> 3;
+    let count = header[2] as usize;
+
+    let slots: Vec<u16> = (0..count)
+        .map_while(|i| read_u16(bytes, offset + 4 + i * 2))
+        .collect();
+
+    // The code array is padded to an even number of slots
+    let tail = offset + 4 + count.next_multiple_of(2) * 2;
+    let mut handler = None;
+    let mut chained = None;
+    if flags & UNW_FLAG_CHAININFO != 0 {
+        chained = Some((
+            read_u32(bytes, tail)?,
+            read_u32(bytes, tail + 4)?,
+            read_u32(bytes, tail + 8)?,
+        ));
+    } else if flags & (UNW_FLAG_EHANDLER | UNW_FLAG_UHANDLER) != 0 {
+        handler = read_u32(bytes, tail);
+    }
+
+    Some(UnwindInfo {
+        version,
+        flags,
+        size_of_prolog: header[1],
+        frame_register: header[3] & 0x0F,
+        frame_offset: header[3] >> 4,
+        codes: decode_codes(&slots, version),
+        handler,
+        chained,
+    })
+}
+
+/// Parses the RUNTIME_FUNCTION table, only meaningful for PE32+ images
+pub fn parse_exceptions(bytes: &[u8], pe_header: &PEHeader) -> Vec<RuntimeFunction> {
+    if !pe::is_pe64(pe_header) {
+        return vec![];
+    }
+    let Some(dir) = pe::data_directory(pe_header, DIR_EXCEPTION) else {
+        return vec![];
+    };
+    let Some(start) = pe::rva_to_offset(pe_header, dir.virtual_address) else {
+        return vec![];
+    };
+
+    (0..dir.size as usize / RUNTIME_FUNCTION_SIZE)
+        .map_while(|i| {
+            let offset = start + i * RUNTIME_FUNCTION_SIZE;
+            let begin_address = read_u32(bytes, offset)?;
+            let end_address = read_u32(bytes, offset + 4)?;
+            let unwind_info_address = read_u32(bytes, offset + 8)?;
+            Some(RuntimeFunction {
+                offset,
+                begin_address,
+                end_address,
+                unwind_info_address,
+                unwind_info: parse_unwind_info(bytes, pe_header, unwind_info_address),
+            })
+        })
+        .filter(|func| func.begin_address != 0 || func.end_address != 0)
+        .collect()
+}
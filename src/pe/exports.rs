@@ -0,0 +1,108 @@
+use pe_parse::PEHeader;
+use serde::Serialize;
+
+use crate::pe::{self, DIR_EXPORT, read_cstr, read_u16, read_u32};
+
+const MAX_EXPORTS: u32 = 0x10000;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ExportDirectory {
+    pub offset: usize,
+    pub characteristics: u32,
+    pub time_date_stamp: u32,
+    pub major_version: u16,
+    pub minor_version: u16,
+    pub name_rva: u32,
+    pub name: String,
+    pub ordinal_base: u32,
+    pub number_of_functions: u32,
+    pub number_of_names: u32,
+    pub address_of_functions: u32,
+    pub address_of_names: u32,
+    pub address_of_name_ordinals: u32,
+    pub exports: Vec<ExportedFunction>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ExportedFunction {
+    pub ordinal: u32,
+    pub rva: u32,
+    pub name: Option<String>,
+    pub forwarder: Option<String>,
+}
+
+pub fn parse_exports(bytes: &[u8], pe_header: &PEHeader) -> Option<ExportDirectory> {
+    let dir = pe::data_directory(pe_header, DIR_EXPORT)?;
+    let offset = pe::rva_to_offset(pe_header, dir.virtual_address)?;
+
+    let name_rva = read_u32(bytes, offset + 12)?;
+    let ordinal_base = read_u32(bytes, offset + 16)?;
+    let number_of_functions = read_u32(bytes, offset + 20)?;
+    let number_of_names = read_u32(bytes, offset + 24)?;
+    let address_of_functions = read_u32(bytes, offset + 28)?;
+    let address_of_names = read_u32(bytes, offset + 32)?;
+    let address_of_name_ordinals = read_u32(bytes, offset + 36)?;
+
+    let name = pe::rva_to_offset(pe_header, name_rva)
+        .and_then(|name_offset| read_cstr(bytes, name_offset, 256))
+        .unwrap_or_default();
+
+    let functions_offset = pe::rva_to_offset(pe_header, address_of_functions);
+    let mut exports: Vec<ExportedFunction> = (0..number_of_functions.min(MAX_EXPORTS))
+        .map_while(|i| {
+            let rva = read_u32(bytes, functions_offset? + i as usize * 4)?;
+            Some(ExportedFunction {
+                ordinal: ordinal_base.wrapping_add(i),
+                rva,
+                name: None,
+                forwarder: None,
+            })
+        })
+        .collect();
+
+    // Names point into the function array through the ordinal table
+    let names_offset = pe::rva_to_offset(pe_header, address_of_names);
+    let ordinals_offset = pe::rva_to_offset(pe_header, address_of_name_ordinals);
+    if let (Some(names_offset), Some(ordinals_offset)) = (names_offset, ordinals_offset) {
+        for i in 0..number_of_names.min(MAX_EXPORTS) as usize {
+            let (Some(name_rva), Some(index)) = (
+                read_u32(bytes, names_offset + i * 4),
+                read_u16(bytes, ordinals_offset + i * 2),
+            ) else {
+                break;
+            };
+            let Some(export) = exports.get_mut(index as usize) else {
+                continue;
+            };
+            export.name = pe::rva_to_offset(pe_header, name_rva)
+                .and_then(|name_offset| read_cstr(bytes, name_offset, 1024));
+        }
+    }
+
+    // A function RVA inside the export directory is a forwarder string
+    let dir_end = dir.virtual_address.saturating_add(dir.size);
+    for export in exports.iter_mut() {
+        if export.rva >= dir.virtual_address && export.rva < dir_end {
+            export.forwarder = pe::rva_to_offset(pe_header, export.rva)
+                .and_then(|fwd_offset| read_cstr(bytes, fwd_offset, 1024));
+        }
+    }
+    exports.retain(|export| export.rva != 0);
+
+    Some(ExportDirectory {
+        offset,
+        characteristics: read_u32(bytes, offset)?,
+        time_date_stamp: read_u32(bytes, offset + 4)?,
+        major_version: read_u16(bytes, offset + 8)?,
+        minor_version: read_u16(bytes, offset + 10)?,
+        name_rva,
+        name,
+        ordinal_base,
+        number_of_functions,
+        number_of_names,
+        address_of_functions,
+        address_of_names,
+        address_of_name_ordinals,
+        exports,
+    })
+}
@@ -0,0 +1,225 @@
+/// (bit, name, description) for a single flag
+pub type Flag = (u32, &'static str, &'static str);
+
+pub const FILE_FLAGS: &[Flag] = &[
+    (0x0001, "RELOCS_STRIPPED", "Relocation info stripped"),
+    (0x0002, "EXECUTABLE_IMAGE", "File is executable"),
+    (0x0004, "LINE_NUMS_STRIPPED", "COFF line numbers stripped"),
+    (0x0008, "LOCAL_SYMS_STRIPPED", "COFF local symbols stripped"),
+    (
+        0x0010,
+        "AGGRESSIVE_WS_TRIM",
+        "Aggressively trim working set",
+    ),
+    (
+        0x0020,
+        "LARGE_ADDRESS_AWARE",
+        "Can handle addresses above 2 GB",
+    ),
+    (0x0080, "BYTES_REVERSED_LO", "Little endian, deprecated"),
+    (0x0100, "32BIT_MACHINE", "32 bit word machine"),
+    (0x0200, "DEBUG_STRIPPED", "Debug info stripped"),
+    (
+        0x0400,
+        "REMOVABLE_RUN_FROM_SWAP",
+        "Copy to swap if on removable media",
+    ),
+    (
+        0x0800,
+        "NET_RUN_FROM_SWAP",
+        "Copy to swap if on network media",
+    ),
+    (0x1000, "SYSTEM", "System file"),
+    (0x2000, "DLL", "File is a DLL"),
+    (0x4000, "UP_SYSTEM_ONLY", "Uniprocessor machines only"),
+    (0x8000, "BYTES_REVERSED_HI", "Big endian, deprecated"),
+];
+
+pub const DLL_FLAGS: &[Flag] = &[
+    (0x0020, "HIGH_ENTROPY_VA", "64 bit high entropy ASLR"),
+    (
+        0x0040,
+        "DYNAMIC_BASE",
+        "Can be relocated at load time (ASLR)",
+    ),
+    (0x0080, "FORCE_INTEGRITY", "Code integrity checks enforced"),
+    (0x0100, "NX_COMPAT", "DEP compatible"),
+    (0x0200, "NO_ISOLATION", "Isolation aware but do not isolate"),
+    (0x0400, "NO_SEH", "No structured exception handling"),
+    (0x0800, "NO_BIND", "Do not bind"),
+    (0x1000, "APPCONTAINER", "Must execute in an AppContainer"),
+    (0x2000, "WDM_DRIVER", "WDM driver"),
+    (0x4000, "GUARD_CF", "Control Flow Guard"),
+    (0x8000, "TERMINAL_SERVER_AWARE", "Terminal server aware"),
+];
+
+pub const SECTION_FLAGS: &[Flag] = &[
+    (0x0000_0008, "TYPE_NO_PAD", "Not padded, object files only"),
+    (0x0000_0020, "CNT_CODE", "Contains executable code"),
+    (
+        0x0000_0040,
+        "CNT_INITIALIZED_DATA",
+        "Contains initialized data",
+    ),
+    (
+        0x0000_0080,
+        "CNT_UNINITIALIZED_DATA",
+        "Contains uninitialized data",
+    ),
+    (0x0000_0100, "LNK_OTHER", "Reserved"),
+    (
+        0x0000_0200,
+        "LNK_INFO",
+        "Comments or other info, object files only",
+    ),
+    (
+        0x0000_0800,
+        "LNK_REMOVE",
+        "Not part of the image, object files only",
+    ),
+    (0x0000_1000, "LNK_COMDAT", "COMDAT data, object files only"),
+    (
+        0x0000_8000,
+        "GPREL",
+        "Referenced through the global pointer",
+    ),
+    (0x0100_0000, "LNK_NRELOC_OVFL", "Extended relocations"),
+    (0x0200_0000, "MEM_DISCARDABLE", "Can be discarded as needed"),
+    (0x0400_0000, "MEM_NOT_CACHED", "Cannot be cached"),
+    (0x0800_0000, "MEM_NOT_PAGED", "Not pageable"),
+    (0x1000_0000, "MEM_SHARED", "Can be shared in memory"),
+    (0x2000_0000, "MEM_EXECUTE", "Can be executed as code"),
+    (0x4000_0000, "MEM_READ", "Can be read"),
+    (0x8000_0000, "MEM_WRITE", "Can be written to"),
+];
+
+const SECTION_ALIGN_MASK: u32 = 0x00F0_0000;
+
+pub fn set_flags(value: u32, table: &'static [Flag]) -> Vec<&'static Flag> {
+    table
+        .iter()
+        .filter(|(bit, _, _)| value & bit != 0)
+        .collect()
+}
+
+pub fn flag_names(value: u32, table: &'static [Flag]) -> String {
+    set_flags(value, table)
+        .iter()
+        .map(|(_, name, _)| *name)
+        .collect::<Vec<&str>>()
+        .join(" | ")
+}
+
+/// One line per set bit, for tooltips
+pub fn flag_tooltip(value: u32, table: &'static [Flag]) -> String {
+    set_flags(value, table)
+        .iter()
+        .map(|(bit, name, description)| format!("0x{:08X} {}: {}", bit, name, description))
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// IMAGE_SCN_ALIGN_* is a 4 bit field rather than a flag, only used in object files
+fn section_alignment(characteristics: u32) -> Option<u32> {
+    match (characteristics & SECTION_ALIGN_MASK) >> 20 {
+        0 => None,
+        n @ 1..=14 => Some(1 << (n - 1)),
+        _ => None,
+    }
+}
+
+pub fn section_flag_names(characteristics: u32) -> String {
+    let mut names = flag_names(characteristics, SECTION_FLAGS);
+    if let Some(align) = section_alignment(characteristics) {
+        if !names.is_empty() {
+            names.push_str(" | ");
+        }
+        names.push_str(&format!("ALIGN_{}BYTES", align));
+    }
+    names
+}
+
+pub fn section_flag_tooltip(characteristics: u32) -> String {
+    let mut tooltip = flag_tooltip(characteristics, SECTION_FLAGS);
+    if let Some(align) = section_alignment(characteristics) {
+        if !tooltip.is_empty() {
+            tooltip.push('\n');
+        }
+        tooltip.push_str(&format!(
+            "0x{:08X} ALIGN_{}BYTES: Align data on a {} byte boundary",
+            characteristics & SECTION_ALIGN_MASK,
+            align,
+            align
+        ));
+    }
+    tooltip
+}
+
+pub fn machine_name(machine: u16) -> &'static str {
+    match machine {
+        0x0000 => "UNKNOWN",
+        0x014C => "I386",
+        0x0162 => "R3000",
+        0x0166 => "R4000",
+        0x0168 => "R10000",
+        0x0169 => "WCEMIPSV2",
+        0x0184 => "ALPHA",
+        0x01A2 => "SH3",
+        0x01A3 => "SH3DSP",
+        0x01A6 => "SH4",
+        0x01A8 => "SH5",
+        0x01C0 => "ARM",
+        0x01C2 => "THUMB",
+        0x01C4 => "ARMNT",
+        0x01D3 => "AM33",
+        0x01F0 => "POWERPC",
+        0x01F1 => "POWERPCFP",
+        0x0200 => "IA64",
+        0x0266 => "MIPS16",
+        0x0284 => "ALPHA64",
+        0x0366 => "MIPSFPU",
+        0x0466 => "MIPSFPU16",
+        0x0520 => "TRICORE",
+        0x0EBC => "EBC",
+        0x5032 => "RISCV32",
+        0x5064 => "RISCV64",
+        0x5128 => "RISCV128",
+        0x6232 => "LOONGARCH32",
+        0x6264 => "LOONGARCH64",
+        0x8664 => "AMD64",
+        0x9041 => "M32R",
+        0xA641 => "ARM64EC",
+        0xA64E => "ARM64X",
+        0xAA64 => "ARM64",
+        _ => "Unknown machine",
+    }
+}
+
+pub fn subsystem_name(subsystem: u16) -> &'static str {
+    match subsystem {
+        0 => "UNKNOWN",
+        1 => "NATIVE",
+        2 => "WINDOWS_GUI",
+        3 => "WINDOWS_CUI",
+        5 => "OS2_CUI",
+        7 => "POSIX_CUI",
+        8 => "NATIVE_WINDOWS",
+        9 => "WINDOWS_CE_GUI",
+        10 => "EFI_APPLICATION",
+        11 => "EFI_BOOT_SERVICE_DRIVER",
+        12 => "EFI_RUNTIME_DRIVER",
+        13 => "EFI_ROM",
+        14 => "XBOX",
+        16 => "WINDOWS_BOOT_APPLICATION",
+        _ => "Unknown subsystem",
+    }
+}
+
+pub fn magic_name(magic: u16) -> &'static str {
+    match magic {
+        0x010B => "PE32",
+        0x020B => "PE32+",
+        0x0107 => "ROM",
+        _ => "Unknown magic",
+    }
+}
@@ -0,0 +1,135 @@
+use pe_parse::PEHeader;
+use serde::Serialize;
+
+use crate::pe::{self, DIR_IMPORT, read_cstr, read_u16, read_u32, read_u64};
+
+const DESCRIPTOR_SIZE: usize = 20;
+const MAX_THUNKS: usize = 0x10000;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportedDll {
+    pub name: String,
+    pub descriptor_offset: usize,
+    pub original_first_thunk: u32,
+    pub time_date_stamp: u32,
+    pub forwarder_chain: u32,
+    pub name_rva: u32,
+    pub first_thunk: u32,
+    pub functions: Vec<ImportedFunction>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportedFunction {
+    pub iat_rva: u32,
+    pub thunk: u64,
+    pub ordinal: Option<u16>,
+    pub hint: Option<u16>,
+    pub name: Option<String>,
+}
+
+pub fn parse_imports(bytes: &[u8], pe_header: &PEHeader) -> Vec<ImportedDll> {
+    let Some(dir) = pe::data_directory(pe_header, DIR_IMPORT) else {
+        return vec![];
+    };
+    let Some(mut offset) = pe::rva_to_offset(pe_header, dir.virtual_address) else {
+        return vec![];
+    };
+
+    let mut dlls = vec![];
+    while let (Some(original_first_thunk), Some(time_date_stamp), Some(forwarder_chain)) = (
+        read_u32(bytes, offset),
+        read_u32(bytes, offset + 4),
+        read_u32(bytes, offset + 8),
+    ) {
+        let (Some(name_rva), Some(first_thunk)) =
+            (read_u32(bytes, offset + 12), read_u32(bytes, offset + 16))
+        else {
+            break;
+        };
+
+        // The import descriptor array ends with a zeroed entry
+        if original_first_thunk == 0 && name_rva == 0 && first_thunk == 0 {
+            break;
+        }
+
+        let name = pe::rva_to_offset(pe_header, name_rva)
+            .and_then(|name_offset| read_cstr(bytes, name_offset, 256))
+            .unwrap_or_else(|| format!("<invalid name 0x{:08X}>", name_rva));
+
+        // Bound or already patched binaries only keep names in the ILT
+        let lookup_rva = if original_first_thunk != 0 {
+            original_first_thunk
+        } else {
+            first_thunk
+        };
+        let functions = parse_thunks(bytes, pe_header, lookup_rva, first_thunk, 0);
+
+        dlls.push(ImportedDll {
+            name,
+            descriptor_offset: offset,
+            original_first_thunk,
+            time_date_stamp,
+            forwarder_chain,
+            name_rva,
+            first_thunk,
+            functions,
+        });
+
+        offset += DESCRIPTOR_SIZE;
+    }
+
+    dlls
+}
+
+/// Walks a thunk array, 4 bytes wide for PE32 and 8 bytes wide for PE32+.
+/// `name_base` is subtracted from name pointers, which legacy delay imports store as VAs
+pub fn parse_thunks(
+    bytes: &[u8],
+    pe_header: &PEHeader,
+    lookup_rva: u32,
+    iat_rva: u32,
+    name_base: u64,
+) -> Vec<ImportedFunction> {
+    let Some(lookup_offset) = pe::rva_to_offset(pe_header, lookup_rva) else {
+        return vec![];
+    };
+    let is_64 = pe::is_pe64(pe_header);
+    let width = if is_64 { 8 } else { 4 };
+    let ordinal_flag = if is_64 { 1u64 << 63 } else { 1u64 << 31 };
+
+    let mut functions = vec![];
+    for i in 0..MAX_THUNKS {
+        let entry_offset = lookup_offset + i * width;
+        let thunk = if is_64 {
+            read_u64(bytes, entry_offset)
+        } else {
+            read_u32(bytes, entry_offset).map(|v| v as u64)
+        };
+        let Some(thunk) = thunk else { break };
+        if thunk == 0 {
+            break;
+        }
+
+        let mut function = ImportedFunction {
+            iat_rva: iat_rva.wrapping_add((i * width) as u32),
+            thunk,
+            ordinal: None,
+            hint: None,
+            name: None,
+        };
+
+        if thunk & ordinal_flag != 0 {
+            function.ordinal = Some((thunk & 0xFFFF) as u16);
+        } else if let Some(hint_offset) = pe::rva_to_offset(
+            pe_header,
+            (thunk.wrapping_sub(name_base) & 0x7FFF_FFFF) as u32,
+        ) {
+            function.hint = read_u16(bytes, hint_offset);
+            function.name = read_cstr(bytes, hint_offset + 2, 1024);
+        }
+
+        functions.push(function);
+    }
+
+    functions
+}
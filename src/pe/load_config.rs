@@ -0,0 +1,373 @@
+use pe_parse::PEHeader;
+use serde::Serialize;
+
+use crate::pe::{self, DIR_LOAD_CONFIG, read_u16, read_u32, read_u64};
+
+const MAX_TABLE_ENTRIES: u64 = 0x10000;
+
+#[derive(Clone, Copy)]
+enum Width {
+    U16,
+    U32,
+    Ptr,
+}
+
+use Width::{Ptr, U16, U32};
+
+// Shared layout of IMAGE_LOAD_CONFIG_DIRECTORY32/64 after the heap fields
+const TAIL_FIELDS: &[(&str, Width, &str)] = &[
+    ("CSDVersion", U16, "Service pack version"),
+    (
+        "DependentLoadFlags",
+        U16,
+        "Default load flags for dependencies",
+    ),
+    ("EditList", Ptr, "Reserved"),
+    ("SecurityCookie", Ptr, "VA of the /GS security cookie"),
+    ("SEHandlerTable", Ptr, "VA of the SafeSEH handler table"),
+    ("SEHandlerCount", Ptr, "Number of SafeSEH handlers"),
+    (
+        "GuardCFCheckFunctionPointer",
+        Ptr,
+        "VA of the CFG check function pointer",
+    ),
+    (
+        "GuardCFDispatchFunctionPointer",
+        Ptr,
+        "VA of the CFG dispatch function pointer",
+    ),
+    ("GuardCFFunctionTable", Ptr, "VA of the CFG function table"),
+    (
+        "GuardCFFunctionCount",
+        Ptr,
+        "Number of CFG function table entries",
+    ),
+    ("GuardFlags", U32, "Control flow guard flags"),
+    ("CodeIntegrity.Flags", U16, "Code integrity flags"),
+    ("CodeIntegrity.Catalog", U16, "Code integrity catalog"),
+    (
+        "CodeIntegrity.CatalogOffset",
+        U32,
+        "Code integrity catalog offset",
+    ),
+    ("CodeIntegrity.Reserved", U32, "Reserved"),
+    (
+        "GuardAddressTakenIatEntryTable",
+        Ptr,
+        "VA of address-taken IAT entries",
+    ),
+    (
+        "GuardAddressTakenIatEntryCount",
+        Ptr,
+        "Number of address-taken IAT entries",
+    ),
+    (
+        "GuardLongJumpTargetTable",
+        Ptr,
+        "VA of the longjmp target table",
+    ),
+    ("GuardLongJumpTargetCount", Ptr, "Number of longjmp targets"),
+    (
+        "DynamicValueRelocTable",
+        Ptr,
+        "VA of the dynamic value relocation table",
+    ),
+    ("CHPEMetadataPointer", Ptr, "VA of the CHPE/ARM64X metadata"),
+    (
+        "GuardRFFailureRoutine",
+        Ptr,
+        "VA of the RFG failure routine",
+    ),
+    (
+        "GuardRFFailureRoutineFunctionPointer",
+        Ptr,
+        "VA of the RFG failure routine pointer",
+    ),
+    (
+        "DynamicValueRelocTableOffset",
+        U32,
+        "Offset of the dynamic relocation table",
+    ),
+    (
+        "DynamicValueRelocTableSection",
+        U16,
+        "Section of the dynamic relocation table",
+    ),
+    ("Reserved2", U16, "Reserved"),
+    (
+        "GuardRFVerifyStackPointerFunctionPointer",
+        Ptr,
+        "VA of the RFG stack check pointer",
+    ),
+    ("HotPatchTableOffset", U32, "Offset of the hot patch table"),
+    ("Reserved3", U32, "Reserved"),
+    (
+        "EnclaveConfigurationPointer",
+        Ptr,
+        "VA of the enclave configuration",
+    ),
+    (
+        "VolatileMetadataPointer",
+        Ptr,
+        "VA of the volatile metadata",
+    ),
+    (
+        "GuardEHContinuationTable",
+        Ptr,
+        "VA of the EH continuation table (CET)",
+    ),
+    (
+        "GuardEHContinuationCount",
+        Ptr,
+        "Number of EH continuation targets",
+    ),
+    (
+        "GuardXFGCheckFunctionPointer",
+        Ptr,
+        "VA of the XFG check function pointer",
+    ),
+    (
+        "GuardXFGDispatchFunctionPointer",
+        Ptr,
+        "VA of the XFG dispatch function pointer",
+    ),
+    (
+        "GuardXFGTableDispatchFunctionPointer",
+        Ptr,
+        "VA of the XFG table dispatch pointer",
+    ),
+    (
+        "CastGuardOsDeterminedFailureMode",
+        Ptr,
+        "VA of the CastGuard failure mode",
+    ),
+    (
+        "GuardMemcpyFunctionPointer",
+        Ptr,
+        "VA of the guarded memcpy pointer",
+    ),
+    (
+        "UmaFunctionPointers",
+        Ptr,
+        "VA of the UMA function pointers",
+    ),
+];
+
+const HEAD_FIELDS: &[(&str, Width, &str)] = &[
+    ("Size", U32, "Size of the structure"),
+    ("TimeDateStamp", U32, "Creation time"),
+    ("MajorVersion", U16, "Major version"),
+    ("MinorVersion", U16, "Minor version"),
+    ("GlobalFlagsClear", U32, "NtGlobalFlag bits to clear"),
+    ("GlobalFlagsSet", U32, "NtGlobalFlag bits to set"),
+    (
+        "CriticalSectionDefaultTimeout",
+        U32,
+        "Default critical section timeout",
+    ),
+    (
+        "DeCommitFreeBlockThreshold",
+        Ptr,
+        "Heap decommit free block threshold",
+    ),
+    (
+        "DeCommitTotalFreeThreshold",
+        Ptr,
+        "Heap decommit total free threshold",
+    ),
+    ("LockPrefixTable", Ptr, "VA of the lock prefix table"),
+    ("MaximumAllocationSize", Ptr, "Maximum heap allocation size"),
+    (
+        "VirtualMemoryThreshold",
+        Ptr,
+        "Heap virtual memory threshold",
+    ),
+];
+
+pub const GUARD_FLAGS: [(u32, &str); 17] = [
+    (0x0000_0100, "CF_INSTRUMENTED"),
+    (0x0000_0200, "CFW_INSTRUMENTED"),
+    (0x0000_0400, "CF_FUNCTION_TABLE_PRESENT"),
+    (0x0000_0800, "SECURITY_COOKIE_UNUSED"),
+    (0x0000_1000, "PROTECT_DELAYLOAD_IAT"),
+    (0x0000_2000, "DELAYLOAD_IAT_IN_ITS_OWN_SECTION"),
+    (0x0000_4000, "CF_EXPORT_SUPPRESSION_INFO_PRESENT"),
+    (0x0000_8000, "CF_ENABLE_EXPORT_SUPPRESSION"),
+    (0x0001_0000, "CF_LONGJUMP_TABLE_PRESENT"),
+    (0x0002_0000, "RF_INSTRUMENTED"),
+    (0x0004_0000, "RF_ENABLE"),
+    (0x0008_0000, "RF_STRICT"),
+    (0x0010_0000, "RETPOLINE_PRESENT"),
+    (0x0040_0000, "EH_CONTINUATION_TABLE_PRESENT"),
+    (0x0080_0000, "XFG_ENABLED"),
+    (0x0100_0000, "CASTGUARD_PRESENT"),
+    (0x0200_0000, "MEMCPY_PRESENT"),
+];
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LoadConfigField {
+    pub offset: usize,
+    pub name: &'static str,
+    pub meaning: &'static str,
+    pub value: u64,
+    pub size: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LoadConfig {
+    pub offset: usize,
+    pub is_64: bool,
+    pub fields: Vec<LoadConfigField>,
+    pub se_handlers: Vec<u32>,
+    pub cf_functions: Vec<(u32, u8)>,
+    pub eh_continuations: Vec<u32>,
+    pub chpe_version: Option<u32>,
+}
+
+impl LoadConfig {
+    pub fn field(&self, name: &str) -> Option<u64> {
+        self.fields
+            .iter()
+            .find(|field| field.name == name)
+            .map(|field| field.value)
+    }
+}
+
+pub fn guard_flag_names(flags: u32) -> Vec<&'static str> {
+    GUARD_FLAGS
+        .iter()
+        .filter(|(bit, _)| flags & bit != 0)
+        .map(|(_, name)| *name)
+        .collect()
+}
+
+/// Reads a table of RVAs, each followed by `stride` metadata bytes
+fn read_rva_table(
+    bytes: &[u8],
+    pe_header: &PEHeader,
+    va: u64,
+    count: u64,
+    stride: usize,
+) -> Vec<(u32, u8)> {
+    let Some(start) = va
+        .checked_sub(pe::image_base(pe_header))
+        .and_then(|rva| u32::try_from(rva).ok())
+        .and_then(|rva| pe::rva_to_offset(pe_header, rva))
+    else {
+        return vec![];
+    };
+    (0..count.min(MAX_TABLE_ENTRIES) as usize)
+        .map_while(|i| {
+            let entry = start + i * (4 + stride);
+            let rva = read_u32(bytes, entry)?;
+            let flags = if stride > 0 {
+                *bytes.get(entry + 4)?
+            } else {
+                0
+            };
+            Some((rva, flags))
+        })
+        .collect()
+}
+
+/// Decodes the load config directory, only reading fields covered by its Size
+pub fn parse_load_config(bytes: &[u8], pe_header: &PEHeader) -> Option<LoadConfig> {
+    let dir = pe::data_directory(pe_header, DIR_LOAD_CONFIG)?;
+    let offset = pe::rva_to_offset(pe_header, dir.virtual_address)?;
+    let size = read_u32(bytes, offset)? as usize;
+    let is_64 = pe::is_pe64(pe_header);
+
+    // The heap flags and affinity mask swap places between the two widths
+    let heap_fields: [(&'static str, Width, &'static str); 2] = if is_64 {
+        [
+            ("ProcessAffinityMask", Ptr, "Process affinity mask"),
+            ("ProcessHeapFlags", U32, "Process heap flags"),
+        ]
+    } else {
+        [
+            ("ProcessHeapFlags", U32, "Process heap flags"),
+            ("ProcessAffinityMask", Ptr, "Process affinity mask"),
+        ]
+    };
+
+    let mut fields = vec![];
+    let mut cursor = 0;
+    let layout = HEAD_FIELDS
+        .iter()
+        .chain(heap_fields.iter())
+        .chain(TAIL_FIELDS.iter());
+    for (name, width, meaning) in layout {
+        let field_size = match width {
+            U16 => 2,
+            U32 => 4,
+            Ptr if is_64 => 8,
+            Ptr => 4,
+        };
+        if cursor + field_size > size {
+            break;
+        }
+        let at = offset + cursor;
+        let value = match field_size {
+            2 => read_u16(bytes, at).map(|v| v as u64),
+            4 => read_u32(bytes, at).map(|v| v as u64),
+            _ => read_u64(bytes, at),
+        }?;
+        fields.push(LoadConfigField {
+            offset: at,
+            name,
+            meaning,
+            value,
+            size: field_size,
+        });
+        cursor += field_size;
+    }
+
+    let mut config = LoadConfig {
+        offset,
+        is_64,
+        fields,
+        se_handlers: vec![],
+        cf_functions: vec![],
+        eh_continuations: vec![],
+        chpe_version: None,
+    };
+
+    if let (Some(table), Some(count)) = (
+        config.field("SEHandlerTable"),
+        config.field("SEHandlerCount"),
+    ) {
+        config.se_handlers = read_rva_table(bytes, pe_header, table, count, 0)
+            .into_iter()
+            .map(|(rva, _)| rva)
+            .collect();
+    }
+
+    let guard_flags = config.field("GuardFlags").unwrap_or(0) as u32;
+    // The top nibble of GuardFlags is the number of extra bytes per CFG entry
+    let stride = (guard_flags >> 28) as usize;
+    if let (Some(table), Some(count)) = (
+        config.field("GuardCFFunctionTable"),
+        config.field("GuardCFFunctionCount"),
+    ) {
+        config.cf_functions = read_rva_table(bytes, pe_header, table, count, stride);
+    }
+    if let (Some(table), Some(count)) = (
+        config.field("GuardEHContinuationTable"),
+        config.field("GuardEHContinuationCount"),
+    ) {
+        config.eh_continuations = read_rva_table(bytes, pe_header, table, count, stride)
+            .into_iter()
+            .map(|(rva, _)| rva)
+            .collect();
+    }
+
+    config.chpe_version = config
+        .field("CHPEMetadataPointer")
+        .filter(|va| *va != 0)
+        .and_then(|va| va.checked_sub(pe::image_base(pe_header)))
+        .and_then(|rva| u32::try_from(rva).ok())
+        .and_then(|rva| pe::rva_to_offset(pe_header, rva))
+        .and_then(|chpe_offset| read_u32(bytes, chpe_offset));
+
+    Some(config)
+}
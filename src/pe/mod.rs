@@ -0,0 +1,122 @@
+pub mod address;
+pub mod authenticode;
+pub mod bound_imports;
+pub mod clr;
+pub mod debug;
+pub mod delay_imports;
+pub mod der;
+pub mod directories;
+pub mod exception;
+pub mod exports;
+pub mod flags;
+pub mod imports;
+pub mod load_config;
+pub mod relocations;
+pub mod report;
+pub mod resources;
+pub mod rich;
+pub mod sections;
+pub mod tls;
+
+use std::{fs, path::Path};
+
+use anyhow::{Context, anyhow};
+use pe_parse::{ImageDataDirectory, OptionalHeaders, PEHeader};
+
+use address::AddressSpace;
+
+pub const DIR_EXPORT: usize = 0;
+pub const DIR_IMPORT: usize = 1;
+pub const DIR_RESOURCE: usize = 2;
+pub const DIR_EXCEPTION: usize = 3;
+pub const DIR_SECURITY: usize = 4;
+pub const DIR_BASERELOC: usize = 5;
+pub const DIR_DEBUG: usize = 6;
+pub const DIR_TLS: usize = 9;
+pub const DIR_LOAD_CONFIG: usize = 10;
+pub const DIR_BOUND_IMPORT: usize = 11;
+pub const DIR_DELAY_IMPORT: usize = 13;
+pub const DIR_COM_DESCRIPTOR: usize = 14;
+
+pub const DIR_NAMES: [&str; 16] = [
+    "Export Directory",
+    "Import Directory",
+    "Resource Directory",
+    "Exception Directory",
+    "Security Directory",
+    "Base Relocation Directory",
+    "Debug Directory",
+    "Architecture Directory",
+    "Global Pointer Directory",
+    "TLS Directory",
+    "Load Configuration Directory",
+    "Bound Import Directory",
+    "Import Address Table Directory",
+    "Delay Import Directory",
+    "COM Descriptor Directory",
+    "Reserved Directory",
+];
+
+/// Reads and parses `path`, for callers that need both the bytes and the headers
+pub fn read_pe(path: &Path) -> anyhow::Result<(Vec<u8>, PEHeader)> {
+    let bytes = fs::read(path).with_context(|| format!("failed to read {}", path.display()))?;
+    let pe_header = pe_parse::parse_pe_header(&bytes[..])
+        .map_err(|err| anyhow!("{} is not a valid PE file: {:?}", path.display(), err))?;
+    Ok((bytes, pe_header))
+}
+
+pub fn read_u16(bytes: &[u8], offset: usize) -> Option<u16> {
+    let slice = bytes.get(offset..offset.checked_add(2)?)?;
+    Some(u16::from_le_bytes(slice.try_into().ok()?))
+}
+
+pub fn read_u32(bytes: &[u8], offset: usize) -> Option<u32> {
+    let slice = bytes.get(offset..offset.checked_add(4)?)?;
+    Some(u32::from_le_bytes(slice.try_into().ok()?))
+}
+
+pub fn read_u64(bytes: &[u8], offset: usize) -> Option<u64> {
+    let slice = bytes.get(offset..offset.checked_add(8)?)?;
+    Some(u64::from_le_bytes(slice.try_into().ok()?))
+}
+
+/// Reads a NUL terminated string, giving up after `max_len` bytes
+pub fn read_cstr(bytes: &[u8], offset: usize, max_len: usize) -> Option<String> {
+    let tail = bytes.get(offset..)?;
+    let tail = &tail[..tail.len().min(max_len)];
+    let end = tail.iter().position(|b| *b == 0)?;
+    Some(String::from_utf8_lossy(&tail[..end]).to_string())
+}
+
+pub fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+pub fn is_pe64(pe_header: &PEHeader) -> bool {
+    matches!(
+        pe_header.nt_header.optional_headers,
+        OptionalHeaders::OptionalHeaders64(_)
+    )
+}
+
+pub fn image_base(pe_header: &PEHeader) -> u64 {
+    match &pe_header.nt_header.optional_headers {
+        OptionalHeaders::OptionalHeaders32(opt32) => opt32.image_base as u64,
+        OptionalHeaders::OptionalHeaders64(opt64) => opt64.image_base,
+    }
+}
+
+/// Returns the data directory at `index`, or `None` if it is empty
+pub fn data_directory(pe_header: &PEHeader, index: usize) -> Option<&ImageDataDirectory> {
+    let dirs = match &pe_header.nt_header.optional_headers {
+        OptionalHeaders::OptionalHeaders32(opt32) => &opt32.data_directory,
+        OptionalHeaders::OptionalHeaders64(opt64) => &opt64.data_directory,
+    };
+    dirs.get(index)
+        .filter(|dir| dir.virtual_address != 0 && dir.size != 0)
+}
+
+/// Maps an RVA to a file offset, `None` if it has no file data
+pub fn rva_to_offset(pe_header: &PEHeader, rva: u32) -> Option<usize> {
+    AddressSpace::new(pe_header).rva_to_offset(rva).ok()
+}
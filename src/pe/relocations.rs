@@ -0,0 +1,111 @@
+use pe_parse::PEHeader;
+use serde::Serialize;
+
+use crate::pe::{self, DIR_BASERELOC, read_u16, read_u32};
+
+pub const IMAGE_REL_BASED_HIGHADJ: u8 = 4;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RelocationBlock {
+    pub offset: usize,
+    pub page_rva: u32,
+    pub block_size: u32,
+    pub entries: Vec<RelocationEntry>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RelocationEntry {
+    pub raw: u16,
+    pub kind: u8,
+    pub offset: u16,
+    pub rva: u32,
+    /// HIGHADJ relocations carry the low 16 bits of the adjustment in the next slot
+    pub param: Option<u16>,
+}
+
+pub fn type_name(kind: u8, machine: u16) -> &'static str {
+    match kind {
+        0 => "ABSOLUTE",
+        1 => "HIGH",
+        2 => "LOW",
+        3 => "HIGHLOW",
+        4 => "HIGHADJ",
+        5 => match machine {
+            0x01C0 | 0x01C2 | 0x01C4 => "ARM_MOV32",
+            0x5032 | 0x5064 | 0x5128 => "RISCV_HIGH20",
+            _ => "MIPS_JMPADDR",
+        },
+        7 => match machine {
+            0x5032 | 0x5064 | 0x5128 => "RISCV_LOW12I",
+            _ => "THUMB_MOV32",
+        },
+        8 => match machine {
+            0x6232 | 0x6264 => "LOONGARCH_MARK_LA",
+            _ => "RISCV_LOW12S",
+        },
+        9 => match machine {
+            0x0200 => "IA64_IMM64",
+            _ => "MIPS_JMPADDR16",
+        },
+        10 => "DIR64",
+        _ => "UNKNOWN",
+    }
+}
+
+pub fn parse_relocations(bytes: &[u8], pe_header: &PEHeader) -> Vec<RelocationBlock> {
+    let Some(dir) = pe::data_directory(pe_header, DIR_BASERELOC) else {
+        return vec![];
+    };
+    let Some(start) = pe::rva_to_offset(pe_header, dir.virtual_address) else {
+        return vec![];
+    };
+    let end = start + dir.size as usize;
+
+    let mut blocks = vec![];
+    let mut offset = start;
+    while offset + 8 <= end {
+        let (Some(page_rva), Some(block_size)) =
+            (read_u32(bytes, offset), read_u32(bytes, offset + 4))
+        else {
+            break;
+        };
+        if block_size < 8 {
+            break;
+        }
+
+        let count = (block_size as usize - 8) / 2;
+        let mut entries = vec![];
+        let mut i = 0;
+        while i < count {
+            let Some(raw) = read_u16(bytes, offset + 8 + i * 2) else {
+                break;
+            };
+            let kind = (raw >> 12) as u8;
+            let entry_offset = raw & 0x0FFF;
+            let param = if kind == IMAGE_REL_BASED_HIGHADJ {
+                i += 1;
+                read_u16(bytes, offset + 8 + i * 2)
+            } else {
+                None
+            };
+            entries.push(RelocationEntry {
+                raw,
+                kind,
+                offset: entry_offset,
+                rva: page_rva.wrapping_add(entry_offset as u32),
+                param,
+            });
+            i += 1;
+        }
+
+        blocks.push(RelocationBlock {
+            offset,
+            page_rva,
+            block_size,
+            entries,
+        });
+        offset += block_size as usize;
+    }
+
+    blocks
+}
@@ -0,0 +1,116 @@
+use md5::Md5;
+use pe_parse::{OptionalHeaders, PEHeader};
+use serde::Serialize;
+use serde_json::Value;
+use sha1::Sha1;
+use sha2::{Digest, Sha256};
+
+use crate::pe::{DIR_NAMES, exports::ExportDirectory, flags, hex, imports::ImportedDll, sections};
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Report {
+    pub file: String,
+    pub size: usize,
+    pub hashes: Hashes,
+    pub dos_header: Value,
+    pub file_header: Value,
+    pub optional_header: Value,
+    pub data_directories: Vec<DataDirectory>,
+    pub sections: Vec<Section>,
+    pub imports: Vec<ImportedDll>,
+    pub exports: Option<ExportDirectory>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Hashes {
+    pub md5: String,
+    pub sha1: String,
+    pub sha256: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DataDirectory {
+    pub name: &'static str,
+    pub virtual_address: u32,
+    pub size: u32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Section {
+    pub name: String,
+    pub virtual_address: u32,
+    pub virtual_size: u32,
+    pub pointer_to_raw_data: u32,
+    pub size_of_raw_data: u32,
+    pub characteristics: u32,
+    pub flags: String,
+    pub entropy: f64,
+}
+
+fn digest<D: Digest>(bytes: &[u8]) -> String {
+    hex(&D::digest(bytes))
+}
+
+/// Collects everything the Info route shows into one serializable value. Imports and
+/// exports are passed in so the GUI can reuse the ones it already parsed.
+pub fn build_report(
+    file: &str,
+    bytes: &[u8],
+    pe_header: &PEHeader,
+    imports: Vec<ImportedDll>,
+    exports: Option<ExportDirectory>,
+) -> Report {
+    let (optional_header, dirs) = match &pe_header.nt_header.optional_headers {
+        OptionalHeaders::OptionalHeaders32(opt32) => {
+            (serde_json::to_value(opt32), &opt32.data_directory)
+        }
+        OptionalHeaders::OptionalHeaders64(opt64) => {
+            (serde_json::to_value(opt64), &opt64.data_directory)
+        }
+    };
+
+    let data_directories = dirs
+        .iter()
+        .zip(DIR_NAMES)
+        .map(|(dir, name)| DataDirectory {
+            name,
+            virtual_address: dir.virtual_address,
+            size: dir.size,
+        })
+        .collect();
+
+    let sections = pe_header
+        .section_headers
+        .iter()
+        .map(|sct| Section {
+            name: String::from_utf8_lossy(&sct.name)
+                .trim_end_matches('\0')
+                .to_string(),
+            virtual_address: sct.virtual_address,
+            virtual_size: sct.virtual_size,
+            pointer_to_raw_data: sct.pointer_to_raw_data,
+            size_of_raw_data: sct.size_of_raw_data,
+            characteristics: sct.characteristics,
+            flags: flags::section_flag_names(sct.characteristics),
+            entropy: sections::entropy(sections::raw_data(bytes, sct)),
+        })
+        .collect();
+
+    Report {
+        file: file.to_string(),
+        size: bytes.len(),
+        hashes: Hashes {
+            md5: digest::<Md5>(bytes),
+            sha1: digest::<Sha1>(bytes),
+            sha256: digest::<Sha256>(bytes),
+        },
+        dos_header: serde_json::to_value(&pe_header.dos_header).unwrap_or_default(),
+        file_header: serde_json::to_value(&pe_header.nt_header.image_file_header)
+            .unwrap_or_default(),
+        optional_header: optional_header.unwrap_or_default(),
+        data_directories,
+        sections,
+        imports,
+        exports,
+    }
+}
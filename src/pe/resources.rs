@@ -0,0 +1,533 @@
+use std::collections::HashSet;
+
+use pe_parse::PEHeader;
+use serde::Serialize;
+
+use crate::pe::{self, DIR_RESOURCE, read_u16, read_u32};
+
+pub const RT_BITMAP: u32 = 2;
+pub const RT_ICON: u32 = 3;
+pub const RT_STRING: u32 = 6;
+pub const RT_GROUP_ICON: u32 = 14;
+pub const RT_VERSION: u32 = 16;
+pub const RT_MANIFEST: u32 = 24;
+
+const MAX_ENTRIES: usize = 0x1000;
+/// Directories may share subdirectories, so the leaves are capped across the whole tree
+const MAX_LEAVES: usize = 0x4000;
+/// VS_VERSIONINFO nests four levels deep, anything past this is malformed
+const MAX_VERSION_DEPTH: usize = 8;
+
+const RESOURCE_TYPES: [(u32, &str); 21] = [
+    (1, "RT_CURSOR"),
+    (2, "RT_BITMAP"),
+    (3, "RT_ICON"),
+    (4, "RT_MENU"),
+    (5, "RT_DIALOG"),
+    (6, "RT_STRING"),
+    (7, "RT_FONTDIR"),
+    (8, "RT_FONT"),
+    (9, "RT_ACCELERATOR"),
+    (10, "RT_RCDATA"),
+    (11, "RT_MESSAGETABLE"),
+    (12, "RT_GROUP_CURSOR"),
+    (14, "RT_GROUP_ICON"),
+    (16, "RT_VERSION"),
+    (17, "RT_DLGINCLUDE"),
+    (19, "RT_PLUGPLAY"),
+    (20, "RT_VXD"),
+    (21, "RT_ANICURSOR"),
+    (22, "RT_ANIICON"),
+    (23, "RT_HTML"),
+    (24, "RT_MANIFEST"),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub enum ResourceId {
+    Id(u32),
+    Name(String),
+}
+
+impl ResourceId {
+    pub fn id(&self) -> Option<u32> {
+        match self {
+            ResourceId::Id(id) => Some(*id),
+            ResourceId::Name(_) => None,
+        }
+    }
+}
+
+impl std::fmt::Display for ResourceId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ResourceId::Id(id) => write!(f, "#{}", id),
+            ResourceId::Name(name) => write!(f, "{}", name),
+        }
+    }
+}
+
+pub fn type_name(id: &ResourceId) -> String {
+    id.id()
+        .and_then(|id| RESOURCE_TYPES.iter().find(|(ty, _)| *ty == id))
+        .map(|(_, name)| name.to_string())
+        .unwrap_or_else(|| id.to_string())
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ResourceDirectory {
+    pub types: Vec<ResourceType>,
+    /// Set when the walk stopped after `MAX_LEAVES` leaves
+    pub truncated: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ResourceType {
+    pub id: ResourceId,
+    pub leaves: Vec<ResourceLeaf>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ResourceLeaf {
+    pub type_id: ResourceId,
+    pub name: ResourceId,
+    pub language: u32,
+    pub rva: u32,
+    pub size: u32,
+    pub code_page: u32,
+    pub offset: Option<usize>,
+}
+
+impl ResourceLeaf {
+    pub fn data<'a>(&self, bytes: &'a [u8]) -> Option<&'a [u8]> {
+        let start = self.offset?;
+        bytes.get(start..start.checked_add(self.size as usize)?)
+    }
+}
+
+struct DirEntry {
+    id: ResourceId,
+    offset: u32,
+    is_dir: bool,
+}
+
+fn read_utf16(bytes: &[u8], offset: usize, len: usize) -> Option<String> {
+    let raw = bytes.get(offset..offset.checked_add(len * 2)?)?;
+    let units: Vec<u16> = raw
+        .chunks_exact(2)
+        .map(|c| u16::from_le_bytes([c[0], c[1]]))
+        .collect();
+    Some(String::from_utf16_lossy(&units))
+}
+
+fn read_dir_entries(bytes: &[u8], base: usize, dir_offset: u32) -> Vec<DirEntry> {
+    let dir = base + dir_offset as usize;
+    let (Some(named), Some(ids)) = (read_u16(bytes, dir + 12), read_u16(bytes, dir + 14)) else {
+        return vec![];
+    };
+    let count = (named as usize + ids as usize).min(MAX_ENTRIES);
+
+    (0..count)
+        .map_while(|i| {
+            let entry = dir + 16 + i * 8;
+            let name = read_u32(bytes, entry)?;
+            let offset = read_u32(bytes, entry + 4)?;
+            let id = if name & 0x8000_0000 != 0 {
+                let name_offset = base + (name & 0x7FFF_FFFF) as usize;
+                let len = read_u16(bytes, name_offset)? as usize;
+                ResourceId::Name(read_utf16(bytes, name_offset + 2, len)?)
+            } else {
+                ResourceId::Id(name)
+            };
+            Some(DirEntry {
+                id,
+                offset: offset & 0x7FFF_FFFF,
+                is_dir: offset & 0x8000_0000 != 0,
+            })
+        })
+        .collect()
+}
+
+/// Walks the type -> name -> language levels of the resource directory
+pub fn parse_resources(bytes: &[u8], pe_header: &PEHeader) -> ResourceDirectory {
+    let Some(dir) = pe::data_directory(pe_header, DIR_RESOURCE) else {
+        return ResourceDirectory::default();
+    };
+    let Some(base) = pe::rva_to_offset(pe_header, dir.virtual_address) else {
+        return ResourceDirectory::default();
+    };
+    walk_directory(bytes, base, |rva| pe::rva_to_offset(pe_header, rva))
+}
+
+/// Each subdirectory is walked at most once, entries pointing back at a
+/// directory that was already visited are skipped
+fn walk_directory(
+    bytes: &[u8],
+    base: usize,
+    rva_to_offset: impl Fn(u32) -> Option<usize>,
+) -> ResourceDirectory {
+    let mut visited = HashSet::from([0]);
+    let mut leaf_count = 0;
+    let mut types = vec![];
+    for type_entry in read_dir_entries(bytes, base, 0) {
+        if !type_entry.is_dir || !visited.insert(type_entry.offset) {
+            continue;
+        }
+        let mut leaves = vec![];
+        for name_entry in read_dir_entries(bytes, base, type_entry.offset) {
+            if !name_entry.is_dir || !visited.insert(name_entry.offset) {
+                continue;
+            }
+            for lang_entry in read_dir_entries(bytes, base, name_entry.offset) {
+                if lang_entry.is_dir {
+                    continue;
+                }
+                if leaf_count == MAX_LEAVES {
+                    types.push(ResourceType {
+                        id: type_entry.id,
+                        leaves,
+                    });
+                    return ResourceDirectory {
+                        types,
+                        truncated: true,
+                    };
+                }
+                let data_entry = base + lang_entry.offset as usize;
+                let (Some(rva), Some(size), Some(code_page)) = (
+                    read_u32(bytes, data_entry),
+                    read_u32(bytes, data_entry + 4),
+                    read_u32(bytes, data_entry + 8),
+                ) else {
+                    continue;
+                };
+                leaf_count += 1;
+                leaves.push(ResourceLeaf {
+                    type_id: type_entry.id.clone(),
+                    name: name_entry.id.clone(),
+                    language: lang_entry.id.id().unwrap_or(0),
+                    rva,
+                    size,
+                    code_page,
+                    offset: rva_to_offset(rva),
+                });
+            }
+        }
+        types.push(ResourceType {
+            id: type_entry.id,
+            leaves,
+        });
+    }
+
+    ResourceDirectory {
+        types,
+        truncated: false,
+    }
+}
+
+pub enum ImagePreviewFormat {
+    Png,
+    Bmp,
+}
+
+pub enum ResourcePreview {
+    Hex,
+    Text(String),
+    Table(Vec<(String, String)>),
+    Image(ImagePreviewFormat, Vec<u8>),
+}
+
+pub fn decode_preview(leaf: &ResourceLeaf, data: &[u8]) -> ResourcePreview {
+    let decoded = match leaf.type_id.id() {
+        Some(RT_MANIFEST) => Some(ResourcePreview::Text(
+            String::from_utf8_lossy(data).to_string(),
+        )),
+        Some(RT_STRING) => decode_string_table(&leaf.name, data).map(ResourcePreview::Table),
+        Some(RT_VERSION) => decode_version_info(data).map(ResourcePreview::Table),
+        Some(RT_GROUP_ICON) => decode_group_icon(data).map(ResourcePreview::Table),
+        Some(RT_ICON) => decode_icon(data),
+        Some(RT_BITMAP) => decode_bitmap(data),
+        _ => None,
+    };
+    decoded.unwrap_or(ResourcePreview::Hex)
+}
+
+/// String tables hold 16 length-prefixed UTF-16 strings per block
+fn decode_string_table(name: &ResourceId, data: &[u8]) -> Option<Vec<(String, String)>> {
+    let block = name.id()?.checked_sub(1)?;
+    let mut rows = vec![];
+    let mut offset = 0;
+    for i in 0..16 {
+        let len = read_u16(data, offset)? as usize;
+        offset += 2;
+        if len > 0 {
+            let string = read_utf16(data, offset, len)?;
+            rows.push(((block * 16 + i).to_string(), string));
+        }
+        offset += len * 2;
+    }
+    Some(rows)
+}
+
+fn align4(offset: usize) -> usize {
+    (offset + 3) & !3
+}
+
+// Walks a VS_VERSIONINFO style block: wLength, wValueLength, wType, szKey, Value, Children
+fn walk_version_block(
+    data: &[u8],
+    start: usize,
+    path: &str,
+    depth: usize,
+    rows: &mut Vec<(String, String)>,
+) {
+    if depth > MAX_VERSION_DEPTH {
+        return;
+    }
+    let Some(length) = read_u16(data, start).map(|l| l as usize) else {
+        return;
+    };
+    let (Some(value_len), Some(value_type)) =
+        (read_u16(data, start + 2), read_u16(data, start + 4))
+    else {
+        return;
+    };
+    let end = (start + length).min(data.len());
+
+    let mut key_end = start + 6;
+    while key_end + 1 < end && read_u16(data, key_end) != Some(0) {
+        key_end += 2;
+    }
+    let key = read_utf16(data, start + 6, (key_end - start - 6) / 2).unwrap_or_default();
+    let value_start = align4(key_end + 2);
+    let path = if path.is_empty() {
+        key.clone()
+    } else {
+        format!("{}/{}", path, key)
+    };
+
+    // wValueLength counts words for text values and bytes for binary ones
+    let value_bytes = if value_type == 1 {
+        value_len as usize * 2
+    } else {
+        value_len as usize
+    };
+
+    if key == "VS_VERSION_INFO" && value_len >= 52 {
+        decode_fixed_file_info(data, value_start, rows);
+    } else if value_type == 1 && value_len > 0 {
+        let value = read_utf16(data, value_start, value_len as usize)
+            .unwrap_or_default()
+            .trim_end_matches('\0')
+            .to_string();
+        rows.push((path.clone(), value));
+    } else if key == "Translation" {
+        let mut off = value_start;
+        while off + 4 <= value_start + value_bytes {
+            if let (Some(lang), Some(cp)) = (read_u16(data, off), read_u16(data, off + 2)) {
+                rows.push((path.clone(), format!("0x{:04X} / {}", lang, cp)));
+            }
+            off += 4;
+        }
+    }
+
+    let mut child = align4(value_start + value_bytes);
+    while child + 6 < end {
+        let Some(child_len) = read_u16(data, child).filter(|l| *l > 0) else {
+            break;
+        };
+        walk_version_block(data, child, &path, depth + 1, rows);
+        child = align4(child + child_len as usize);
+    }
+}
+
+fn decode_fixed_file_info(data: &[u8], offset: usize, rows: &mut Vec<(String, String)>) {
+    let version = |hi: usize, lo: usize| -> Option<String> {
+        let (ms, ls) = (read_u32(data, offset + hi)?, read_u32(data, offset + lo)?);
+        Some(format!(
+            "{}.{}.{}.{}",
+            ms >> 16,
+            ms & 0xFFFF,
+            ls >> 16,
+            ls & 0xFFFF
+        ))
+    };
+    if read_u32(data, offset) != Some(0xFEEF04BD) {
+        return;
+    }
+    if let Some(file_version) = version(8, 12) {
+        rows.push(("FileVersion (fixed)".to_string(), file_version));
+    }
+    if let Some(product_version) = version(16, 20) {
+        rows.push(("ProductVersion (fixed)".to_string(), product_version));
+    }
+    let fields = [
+        ("FileFlags", 28),
+        ("FileOS", 32),
+        ("FileType", 36),
+        ("FileSubtype", 40),
+    ];
+    for (name, field_offset) in fields {
+        if let Some(value) = read_u32(data, offset + field_offset) {
+            rows.push((name.to_string(), format!("0x{:08X}", value)));
+        }
+    }
+}
+
+fn decode_version_info(data: &[u8]) -> Option<Vec<(String, String)>> {
+    let mut rows = vec![];
+    walk_version_block(data, 0, "", 0, &mut rows);
+    (!rows.is_empty()).then_some(rows)
+}
+
+fn decode_group_icon(data: &[u8]) -> Option<Vec<(String, String)>> {
+    let count = read_u16(data, 4)? as usize;
+    (0..count)
+        .map(|i| {
+            let entry = 6 + i * 14;
+            let width = *data.get(entry)?;
+            let height = *data.get(entry + 1)?;
+            let bit_count = read_u16(data, entry + 6)?;
+            let size = read_u32(data, entry + 8)?;
+            let id = read_u16(data, entry + 12)?;
+            // A stored size of 0 means 256 pixels
+            let dim = |d: u8| if d == 0 { 256 } else { d as u32 };
+            Some((
+                format!("RT_ICON #{}", id),
+                format!(
+                    "{}x{}, {} bpp, {} bytes",
+                    dim(width),
+                    dim(height),
+                    bit_count,
+                    size
+                ),
+            ))
+        })
+        .collect()
+}
+
+/// Builds a BMP file from a packed DIB, optionally halving the height for icon masks
+fn dib_to_bmp(dib: &[u8], halve_height: bool) -> Option<Vec<u8>> {
+    let header_size = read_u32(dib, 0)?;
+    let bit_count = read_u16(dib, 14)?;
+    let clr_used = read_u32(dib, 32).unwrap_or(0);
+    let palette_entries = if clr_used != 0 {
+        clr_used
+    } else if bit_count <= 8 {
+        1 << bit_count
+    } else {
+        0
+    };
+    // Both sizes come from the file, a bogus palette size must not overflow
+    let pixel_offset = palette_entries
+        .checked_mul(4)?
+        .checked_add(header_size)?
+        .checked_add(14)?;
+    let file_size = u32::try_from(dib.len()).ok()?.checked_add(14)?;
+
+    let mut dib = dib.to_vec();
+    if halve_height {
+        let height = read_u32(&dib, 8)? as i32 / 2;
+        dib.get_mut(8..12)?.copy_from_slice(&height.to_le_bytes());
+    }
+
+    let mut bmp = Vec::with_capacity(dib.len() + 14);
+    bmp.extend_from_slice(b"BM");
+    bmp.extend_from_slice(&file_size.to_le_bytes());
+    bmp.extend_from_slice(&[0; 4]);
+    bmp.extend_from_slice(&pixel_offset.to_le_bytes());
+    bmp.extend_from_slice(&dib);
+    Some(bmp)
+}
+
+fn decode_icon(data: &[u8]) -> Option<ResourcePreview> {
+    if data.starts_with(b"\x89PNG") {
+        return Some(ResourcePreview::Image(
+            ImagePreviewFormat::Png,
+            data.to_vec(),
+        ));
+    }
+    let bmp = dib_to_bmp(data, true)?;
+    Some(ResourcePreview::Image(ImagePreviewFormat::Bmp, bmp))
+}
+
+fn decode_bitmap(data: &[u8]) -> Option<ResourcePreview> {
+    let bmp = dib_to_bmp(data, false)?;
+    Some(ResourcePreview::Image(ImagePreviewFormat::Bmp, bmp))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SUBDIR: u32 = 0x8000_0000;
+
+    /// Writes a directory of ID entries at `at`, each entry a (name, offset) pair
+    fn write_dir(buf: &mut Vec<u8>, at: usize, entries: &[(u32, u32)]) {
+        let end = at + 16 + entries.len() * 8;
+        if buf.len() < end {
+            buf.resize(end, 0);
+        }
+        buf[at + 14..at + 16].copy_from_slice(&(entries.len() as u16).to_le_bytes());
+        for (i, (name, offset)) in entries.iter().enumerate() {
+            let entry = at + 16 + i * 8;
+            buf[entry..entry + 4].copy_from_slice(&name.to_le_bytes());
+            buf[entry + 4..entry + 8].copy_from_slice(&offset.to_le_bytes());
+        }
+    }
+
+    fn write_data_entry(buf: &mut Vec<u8>, at: usize) {
+        if buf.len() < at + 16 {
+            buf.resize(at + 16, 0);
+        }
+        buf[at..at + 4].copy_from_slice(&0x1000u32.to_le_bytes());
+        buf[at + 4..at + 8].copy_from_slice(&4u32.to_le_bytes());
+    }
+
+    #[test]
+    fn shared_subdirectories_are_walked_once() {
+        let mut buf = vec![];
+        // The second type points back at the root
+        write_dir(
+            &mut buf,
+            0,
+            &[(RT_ICON, SUBDIR | 0x40), (RT_BITMAP, SUBDIR)],
+        );
+        // Every name points at the same language directory
+        let names: Vec<(u32, u32)> = (1..=100).map(|id| (id, SUBDIR | 0x800)).collect();
+        write_dir(&mut buf, 0x40, &names);
+        write_dir(&mut buf, 0x800, &[(0x409, 0x900), (0x407, 0x900)]);
+        write_data_entry(&mut buf, 0x900);
+
+        let dir = walk_directory(&buf, 0, |rva| Some(rva as usize));
+        assert!(!dir.truncated);
+        assert_eq!(dir.types.len(), 1);
+        assert_eq!(dir.types[0].leaves.len(), 2);
+        assert_eq!(dir.types[0].leaves[0].name, ResourceId::Id(1));
+    }
+
+    #[test]
+    fn leaves_are_capped_across_directories() {
+        let mut buf = vec![];
+        let lang_dir_len = 16 + MAX_ENTRIES * 8;
+        let lang_dirs: Vec<usize> = (0..5).map(|i| 0x100 + i * lang_dir_len).collect();
+        let data_entry = 0x100 + 5 * lang_dir_len;
+        write_dir(&mut buf, 0, &[(RT_ICON, SUBDIR | 0x40)]);
+        let names: Vec<(u32, u32)> = lang_dirs
+            .iter()
+            .enumerate()
+            .map(|(i, at)| (i as u32 + 1, SUBDIR | *at as u32))
+            .collect();
+        write_dir(&mut buf, 0x40, &names);
+        let langs: Vec<(u32, u32)> = (0..MAX_ENTRIES as u32)
+            .map(|lang| (lang, data_entry as u32))
+            .collect();
+        for at in &lang_dirs {
+            write_dir(&mut buf, *at, &langs);
+        }
+        write_data_entry(&mut buf, data_entry);
+
+        let dir = walk_directory(&buf, 0, |rva| Some(rva as usize));
+        assert!(dir.truncated);
+        assert_eq!(dir.types.len(), 1);
+        assert_eq!(dir.types[0].leaves.len(), MAX_LEAVES);
+    }
+}
@@ -0,0 +1,474 @@
+use pe_parse::PEHeader;
+use serde::Serialize;
+
+use crate::pe::read_u32;
+
+const RICH_MARKER: u32 = 0x6863_6952;
+const DANS_MARKER: u32 = 0x536E_6144;
+const DOS_HEADER_SIZE: usize = 0x40;
+/// e_lfanew is excluded from the checksum
+const E_LFANEW_OFFSET: usize = 0x3C;
+
+/// Product ids from the linker's prodidTag enumeration, indexed by id
+const PRODUCT_NAMES: [&str; 0x106] = [
+    "Unknown",
+    "Import0",
+    "Linker510",
+    "Cvtomf510",
+    "Linker600",
+    "Cvtomf600",
+    "Cvtres500",
+    "Utc11_Basic",
+    "Utc11_C",
+    "Utc12_Basic",
+    "Utc12_C",
+    "Utc12_CPP",
+    "AliasObj60",
+    "VisualBasic60",
+    "Masm613",
+    "Masm710",
+    "Linker511",
+    "Cvtomf511",
+    "Masm614",
+    "Linker512",
+    "Cvtomf512",
+    "Utc12_C_Std",
+    "Utc12_CPP_Std",
+    "Utc12_C_Book",
+    "Utc12_CPP_Book",
+    "Implib700",
+    "Cvtomf700",
+    "Utc13_Basic",
+    "Utc13_C",
+    "Utc13_CPP",
+    "Linker610",
+    "Cvtomf610",
+    "Linker601",
+    "Cvtomf601",
+    "Utc12_1_Basic",
+    "Utc12_1_C",
+    "Utc12_1_CPP",
+    "Linker620",
+    "Cvtomf620",
+    "AliasObj70",
+    "Linker621",
+    "Cvtomf621",
+    "Masm615",
+    "Utc13_LTCG_C",
+    "Utc13_LTCG_CPP",
+    "Masm620",
+    "ILAsm100",
+    "Utc12_2_Basic",
+    "Utc12_2_C",
+    "Utc12_2_CPP",
+    "Utc12_2_C_Std",
+    "Utc12_2_CPP_Std",
+    "Utc12_2_C_Book",
+    "Utc12_2_CPP_Book",
+    "Implib622",
+    "Cvtomf622",
+    "Cvtres501",
+    "Utc13_C_Std",
+    "Utc13_CPP_Std",
+    "Cvtpgd1300",
+    "Linker622",
+    "Linker700",
+    "Export622",
+    "Export700",
+    "Masm700",
+    "Utc13_POGO_I_C",
+    "Utc13_POGO_I_CPP",
+    "Utc13_POGO_O_C",
+    "Utc13_POGO_O_CPP",
+    "Cvtres700",
+    "Cvtres710p",
+    "Linker710p",
+    "Cvtomf710p",
+    "Export710p",
+    "Implib710p",
+    "Masm710p",
+    "Utc1310p_C",
+    "Utc1310p_CPP",
+    "Utc1310p_C_Std",
+    "Utc1310p_CPP_Std",
+    "Utc1310p_LTCG_C",
+    "Utc1310p_LTCG_CPP",
+    "Utc1310p_POGO_I_C",
+    "Utc1310p_POGO_I_CPP",
+    "Utc1310p_POGO_O_C",
+    "Utc1310p_POGO_O_CPP",
+    "Linker624",
+    "Cvtomf624",
+    "Export624",
+    "Implib624",
+    "Linker710",
+    "Cvtomf710",
+    "Export710",
+    "Implib710",
+    "Cvtres710",
+    "Utc1310_C",
+    "Utc1310_CPP",
+    "Utc1310_C_Std",
+    "Utc1310_CPP_Std",
+    "Utc1310_LTCG_C",
+    "Utc1310_LTCG_CPP",
+    "Utc1310_POGO_I_C",
+    "Utc1310_POGO_I_CPP",
+    "Utc1310_POGO_O_C",
+    "Utc1310_POGO_O_CPP",
+    "AliasObj710",
+    "AliasObj710p",
+    "Cvtpgd1310",
+    "Cvtpgd1310p",
+    "Utc1400_C",
+    "Utc1400_CPP",
+    "Utc1400_C_Std",
+    "Utc1400_CPP_Std",
+    "Utc1400_LTCG_C",
+    "Utc1400_LTCG_CPP",
+    "Utc1400_POGO_I_C",
+    "Utc1400_POGO_I_CPP",
+    "Utc1400_POGO_O_C",
+    "Utc1400_POGO_O_CPP",
+    "Cvtpgd1400",
+    "Linker800",
+    "Cvtomf800",
+    "Export800",
+    "Implib800",
+    "Cvtres800",
+    "Masm800",
+    "AliasObj800",
+    "PhoenixPrerelease",
+    "Utc1400_CVTCIL_C",
+    "Utc1400_CVTCIL_CPP",
+    "Utc1400_LTCG_MSIL",
+    "Utc1500_C",
+    "Utc1500_CPP",
+    "Utc1500_C_Std",
+    "Utc1500_CPP_Std",
+    "Utc1500_CVTCIL_C",
+    "Utc1500_CVTCIL_CPP",
+    "Utc1500_LTCG_C",
+    "Utc1500_LTCG_CPP",
+    "Utc1500_LTCG_MSIL",
+    "Utc1500_POGO_I_C",
+    "Utc1500_POGO_I_CPP",
+    "Utc1500_POGO_O_C",
+    "Utc1500_POGO_O_CPP",
+    "Cvtpgd1500",
+    "Linker900",
+    "Export900",
+    "Implib900",
+    "Cvtres900",
+    "Masm900",
+    "AliasObj900",
+    "Resource",
+    "AliasObj1000",
+    "Cvtpgd1600",
+    "Cvtres1000",
+    "Export1000",
+    "Implib1000",
+    "Linker1000",
+    "Masm1000",
+    "Phx1600_C",
+    "Phx1600_CPP",
+    "Phx1600_CVTCIL_C",
+    "Phx1600_CVTCIL_CPP",
+    "Phx1600_LTCG_C",
+    "Phx1600_LTCG_CPP",
+    "Phx1600_LTCG_MSIL",
+    "Phx1600_POGO_I_C",
+    "Phx1600_POGO_I_CPP",
+    "Phx1600_POGO_O_C",
+    "Phx1600_POGO_O_CPP",
+    "Utc1600_C",
+    "Utc1600_CPP",
+    "Utc1600_CVTCIL_C",
+    "Utc1600_CVTCIL_CPP",
+    "Utc1600_LTCG_C",
+    "Utc1600_LTCG_CPP",
+    "Utc1600_LTCG_MSIL",
+    "Utc1600_POGO_I_C",
+    "Utc1600_POGO_I_CPP",
+    "Utc1600_POGO_O_C",
+    "Utc1600_POGO_O_CPP",
+    "AliasObj1010",
+    "Cvtpgd1610",
+    "Cvtres1010",
+    "Export1010",
+    "Implib1010",
+    "Linker1010",
+    "Masm1010",
+    "Utc1610_C",
+    "Utc1610_CPP",
+    "Utc1610_CVTCIL_C",
+    "Utc1610_CVTCIL_CPP",
+    "Utc1610_LTCG_C",
+    "Utc1610_LTCG_CPP",
+    "Utc1610_LTCG_MSIL",
+    "Utc1610_POGO_I_C",
+    "Utc1610_POGO_I_CPP",
+    "Utc1610_POGO_O_C",
+    "Utc1610_POGO_O_CPP",
+    "AliasObj1100",
+    "Cvtpgd1700",
+    "Cvtres1100",
+    "Export1100",
+    "Implib1100",
+    "Linker1100",
+    "Masm1100",
+    "Utc1700_C",
+    "Utc1700_CPP",
+    "Utc1700_CVTCIL_C",
+    "Utc1700_CVTCIL_CPP",
+    "Utc1700_LTCG_C",
+    "Utc1700_LTCG_CPP",
+    "Utc1700_LTCG_MSIL",
+    "Utc1700_POGO_I_C",
+    "Utc1700_POGO_I_CPP",
+    "Utc1700_POGO_O_C",
+    "Utc1700_POGO_O_CPP",
+    "AliasObj1200",
+    "Cvtpgd1800",
+    "Cvtres1200",
+    "Export1200",
+    "Implib1200",
+    "Linker1200",
+    "Masm1200",
+    "Utc1800_C",
+    "Utc1800_CPP",
+    "Utc1800_CVTCIL_C",
+    "Utc1800_CVTCIL_CPP",
+    "Utc1800_LTCG_C",
+    "Utc1800_LTCG_CPP",
+    "Utc1800_LTCG_MSIL",
+    "Utc1800_POGO_I_C",
+    "Utc1800_POGO_I_CPP",
+    "Utc1800_POGO_O_C",
+    "Utc1800_POGO_O_CPP",
+    "AliasObj1210",
+    "Cvtpgd1810",
+    "Cvtres1210",
+    "Export1210",
+    "Implib1210",
+    "Linker1210",
+    "Masm1210",
+    "Utc1810_C",
+    "Utc1810_CPP",
+    "Utc1810_CVTCIL_C",
+    "Utc1810_CVTCIL_CPP",
+    "Utc1810_LTCG_C",
+    "Utc1810_LTCG_CPP",
+    "Utc1810_LTCG_MSIL",
+    "Utc1810_POGO_I_C",
+    "Utc1810_POGO_I_CPP",
+    "Utc1810_POGO_O_C",
+    "Utc1810_POGO_O_CPP",
+    "AliasObj1400",
+    "Cvtpgd1900",
+    "Cvtres1400",
+    "Export1400",
+    "Implib1400",
+    "Linker1400",
+    "Masm1400",
+    "Utc1900_C",
+    "Utc1900_CPP",
+];
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RichHeader {
+    /// File offset of the "DanS" marker
+    pub offset: usize,
+    /// File offset of the "Rich" marker
+    pub end: usize,
+    pub key: u32,
+    pub computed_checksum: u32,
+    pub entries: Vec<RichEntry>,
+}
+
+impl RichHeader {
+    pub fn checksum_valid(&self) -> bool {
+        self.computed_checksum == self.key
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RichEntry {
+    pub offset: usize,
+    pub comp_id: u32,
+    pub product_id: u16,
+    pub build: u16,
+    pub count: u32,
+}
+
+impl RichEntry {
+    pub fn product_name(&self) -> String {
+        match PRODUCT_NAMES.get(self.product_id as usize) {
+            Some(name) => name.to_string(),
+            None => format!("prodid 0x{:04X}", self.product_id),
+        }
+    }
+
+    /// Visual Studio release, from the product id range and for 14.x toolsets the build
+    pub fn vs_version(&self) -> &'static str {
+        match self.product_id {
+            0x0000 | 0x0001 => "",
+            0x0002..=0x0005 => "VS97",
+            0x0006..=0x0059 => "VS6 / VS2002",
+            0x005A..=0x006C => "VS2003",
+            0x006D..=0x0082 => "VS2005",
+            0x0083..=0x0097 => "VS2008",
+            0x0098..=0x00C6 => "VS2010",
+            0x00C7..=0x00D8 => "VS2012",
+            0x00D9..=0x00FC => "VS2013",
+            _ => match self.build {
+                0..=25016 => "VS2015",
+                25017..=27508 => "VS2017",
+                27509..=30159 => "VS2019",
+                _ => "VS2022",
+            },
+        }
+    }
+}
+
+/// Locates the Rich header between the DOS stub and the PE signature and
+/// decodes it with the XOR key that follows the "Rich" marker
+pub fn parse_rich(bytes: &[u8], pe_header: &PEHeader) -> Option<RichHeader> {
+    decode_rich(bytes, pe_header.dos_header.e_lfanew as usize)
+}
+
+fn decode_rich(bytes: &[u8], e_lfanew: usize) -> Option<RichHeader> {
+    let limit = e_lfanew.min(bytes.len());
+    let end = (DOS_HEADER_SIZE..limit.saturating_sub(7))
+        .step_by(4)
+        .find(|offset| read_u32(bytes, *offset) == Some(RICH_MARKER))?;
+    let key = read_u32(bytes, end + 4)?;
+
+    let offset = (DOS_HEADER_SIZE..end)
+        .step_by(4)
+        .rev()
+        .find(|offset| read_u32(bytes, *offset).map(|v| v ^ key) == Some(DANS_MARKER))?;
+
+    // Three zeroed padding dwords follow the "DanS" marker
+    let entries: Vec<RichEntry> = (offset + 16..end)
+        .step_by(8)
+        .map_while(|entry_offset| {
+            let comp_id = read_u32(bytes, entry_offset)? ^ key;
+            let count = read_u32(bytes, entry_offset + 4)? ^ key;
+            Some(RichEntry {
+                offset: entry_offset,
+                comp_id,
+                product_id: (comp_id >> 16) as u16,
+                build: comp_id as u16,
+                count,
+            })
+        })
+        .collect();
+
+    let mut checksum = offset as u32;
+    for (i, byte) in bytes[..offset].iter().enumerate() {
+        if (E_LFANEW_OFFSET..E_LFANEW_OFFSET + 4).contains(&i) {
+            continue;
+        }
+        checksum = checksum.wrapping_add((*byte as u32).rotate_left(i as u32));
+    }
+    for entry in &entries {
+        checksum = checksum.wrapping_add(entry.comp_id.rotate_left(entry.count));
+    }
+
+    Some(RichHeader {
+        offset,
+        end,
+        key,
+        computed_checksum: checksum,
+        entries,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY: u32 = 0xE2AC_627F;
+    const ENTRIES: [(u32, u32); 3] = [(0x0102_5E97, 1), (0x0104_5E97, 12), (0x0093_7809, 5)];
+
+    /// DOS header and the standard MSVC stub, followed by a Rich header
+    /// XORed with its checksum and e_lfanew pointing past it
+    fn image() -> Vec<u8> {
+        let mut bytes = vec![0; 0xC0];
+        bytes[..0x1A].copy_from_slice(&[
+            0x4D, 0x5A, 0x90, 0x00, 0x03, 0x00, 0x00, 0x00, 0x04, 0x00, 0x00, 0x00, 0xFF, 0xFF,
+            0x00, 0x00, 0xB8, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x40, 0x00,
+        ]);
+        bytes[0x3C..0x40].copy_from_slice(&0xC0u32.to_le_bytes());
+        bytes[0x40..0x4E].copy_from_slice(&[
+            0x0E, 0x1F, 0xBA, 0x0E, 0x00, 0xB4, 0x09, 0xCD, 0x21, 0xB8, 0x01, 0x4C, 0xCD, 0x21,
+        ]);
+        bytes[0x4E..0x79].copy_from_slice(b"This program cannot be run in DOS mode.\r\r\n$");
+
+        let mut dwords = vec![DANS_MARKER ^ KEY, KEY, KEY, KEY];
+        for (comp_id, count) in ENTRIES {
+            dwords.extend([comp_id ^ KEY, count ^ KEY]);
+        }
+        dwords.extend([RICH_MARKER, KEY]);
+        for (i, dword) in dwords.iter().enumerate() {
+            bytes[0x80 + i * 4..0x84 + i * 4].copy_from_slice(&dword.to_le_bytes());
+        }
+        bytes
+    }
+
+    #[test]
+    fn decodes_rich_header() {
+        let rich = decode_rich(&image(), 0xC0).unwrap();
+        assert_eq!(rich.offset, 0x80);
+        assert_eq!(rich.end, 0xA8);
+        assert_eq!(rich.key, KEY);
+        assert_eq!(rich.computed_checksum, KEY);
+        assert!(rich.checksum_valid());
+
+        let entries: Vec<_> = rich
+            .entries
+            .iter()
+            .map(|entry| (entry.product_name(), entry.build, entry.count))
+            .collect();
+        assert_eq!(
+            entries,
+            [
+                ("Linker1400".to_string(), 24215, 1),
+                ("Utc1900_C".to_string(), 24215, 12),
+                ("Implib900".to_string(), 30729, 5),
+            ]
+        );
+    }
+
+    #[test]
+    fn checksum_ignores_e_lfanew() {
+        let mut bytes = image();
+        bytes[0x3C] = 0xC4;
+        bytes.extend([0; 4]);
+        assert!(decode_rich(&bytes, 0xC4).unwrap().checksum_valid());
+
+        let mut bytes = image();
+        bytes[0x60] ^= 1;
+        assert!(!decode_rich(&bytes, 0xC0).unwrap().checksum_valid());
+    }
+
+    #[test]
+    fn product_names() {
+        let entry = |product_id| RichEntry {
+            offset: 0,
+            comp_id: (product_id as u32) << 16,
+            product_id,
+            build: 0,
+            count: 0,
+        };
+        assert_eq!(entry(0x0082).product_name(), "Utc1400_LTCG_MSIL");
+        assert_eq!(entry(0x0091).product_name(), "Linker900");
+        assert_eq!(entry(0x009D).product_name(), "Linker1000");
+        assert_eq!(entry(0x00CC).product_name(), "Linker1100");
+        assert_eq!(entry(0x00DE).product_name(), "Linker1200");
+        assert_eq!(entry(0x00FE).product_name(), "Cvtpgd1900");
+        assert_eq!(entry(0x0105).product_name(), "Utc1900_CPP");
+        assert_eq!(entry(0x0106).product_name(), "prodid 0x0106");
+    }
+}
@@ -0,0 +1,47 @@
+use pe_parse::{PEHeader, SectionHeader};
+
+use crate::pe::address::AddressSpace;
+
+const IMAGE_SCN_CNT_CODE: u32 = 0x0000_0020;
+const IMAGE_SCN_MEM_EXECUTE: u32 = 0x2000_0000;
+
+/// Raw bytes backing a section, clamped to the end of the file
+pub fn raw_data<'a>(bytes: &'a [u8], sct: &SectionHeader) -> &'a [u8] {
+    let start = (sct.pointer_to_raw_data as usize).min(bytes.len());
+    let end = start
+        .saturating_add(sct.size_of_raw_data as usize)
+        .min(bytes.len());
+    &bytes[start..end]
+}
+
+/// VA and raw bytes of each section holding code, in section table order
+pub fn code_sections<'a>(bytes: &'a [u8], pe_header: &PEHeader) -> Vec<(u64, &'a [u8])> {
+    let space = AddressSpace::new(pe_header);
+    pe_header
+        .section_headers
+        .iter()
+        .filter(|sct| sct.characteristics & (IMAGE_SCN_CNT_CODE | IMAGE_SCN_MEM_EXECUTE) != 0)
+        .map(|sct| (space.rva_to_va(sct.virtual_address), raw_data(bytes, sct)))
+        .filter(|(_, data)| !data.is_empty())
+        .collect()
+}
+
+/// Shannon entropy in bits per byte, from 0.0 (constant) to 8.0 (random)
+pub fn entropy(data: &[u8]) -> f64 {
+    if data.is_empty() {
+        return 0.0;
+    }
+    let mut counts = [0usize; 256];
+    for b in data {
+        counts[*b as usize] += 1;
+    }
+    let len = data.len() as f64;
+    counts
+        .iter()
+        .filter(|count| **count != 0)
+        .map(|count| {
+            let p = *count as f64 / len;
+            -p * p.log2()
+        })
+        .sum()
+}
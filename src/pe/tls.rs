@@ -0,0 +1,78 @@
+use pe_parse::PEHeader;
+use serde::Serialize;
+
+use crate::pe::{self, DIR_TLS, read_u32, read_u64};
+
+const MAX_CALLBACKS: usize = 0x400;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TlsDirectory {
+    pub offset: usize,
+    pub is_64: bool,
+    pub start_address_of_raw_data: u64,
+    pub end_address_of_raw_data: u64,
+    pub address_of_index: u64,
+    pub address_of_callbacks: u64,
+    pub size_of_zero_fill: u32,
+    pub characteristics: u32,
+    pub callbacks: Vec<TlsCallback>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TlsCallback {
+    pub va: u64,
+    pub rva: Option<u32>,
+    pub file_offset: Option<usize>,
+}
+
+/// Converts a VA to a file offset by going through the RVA
+fn va_to_offset(pe_header: &PEHeader, va: u64) -> (Option<u32>, Option<usize>) {
+    let rva = va
+        .checked_sub(pe::image_base(pe_header))
+        .and_then(|rva| u32::try_from(rva).ok());
+    let offset = rva.and_then(|rva| pe::rva_to_offset(pe_header, rva));
+    (rva, offset)
+}
+
+/// Decodes IMAGE_TLS_DIRECTORY32 or IMAGE_TLS_DIRECTORY64 depending on the optional header
+pub fn parse_tls(bytes: &[u8], pe_header: &PEHeader) -> Option<TlsDirectory> {
+    let dir = pe::data_directory(pe_header, DIR_TLS)?;
+    let offset = pe::rva_to_offset(pe_header, dir.virtual_address)?;
+    let is_64 = pe::is_pe64(pe_header);
+    let width = if is_64 { 8 } else { 4 };
+    let read_ptr = |at: usize| {
+        if is_64 {
+            read_u64(bytes, at)
+        } else {
+            read_u32(bytes, at).map(|v| v as u64)
+        }
+    };
+
+    let address_of_callbacks = read_ptr(offset + width * 3)?;
+    let mut callbacks = vec![];
+    if let (_, Some(array_offset)) = va_to_offset(pe_header, address_of_callbacks) {
+        for i in 0..MAX_CALLBACKS {
+            let Some(va) = read_ptr(array_offset + i * width).filter(|va| *va != 0) else {
+                break;
+            };
+            let (rva, file_offset) = va_to_offset(pe_header, va);
+            callbacks.push(TlsCallback {
+                va,
+                rva,
+                file_offset,
+            });
+        }
+    }
+
+    Some(TlsDirectory {
+        offset,
+        is_64,
+        start_address_of_raw_data: read_ptr(offset)?,
+        end_address_of_raw_data: read_ptr(offset + width)?,
+        address_of_index: read_ptr(offset + width * 2)?,
+        address_of_callbacks,
+        size_of_zero_fill: read_u32(bytes, offset + width * 4)?,
+        characteristics: read_u32(bytes, offset + width * 4 + 4)?,
+        callbacks,
+    })
+}
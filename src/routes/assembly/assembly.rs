@@ -1,23 +1,71 @@
-use std::{ops::Range, path::Path, rc::Rc};
+use std::{
+    ops::Range,
+    rc::Rc,
+    sync::atomic::{AtomicBool, Ordering},
+};
 
 use gpui::{
-    AnyElement, Div, HighlightStyle, Pixels, SharedString, Size, Window, div, prelude::*, px, size,
+    AnyElement, App, Div, HighlightStyle, Pixels, ScrollStrategy, SharedString, Size, Window, div,
+    point, prelude::*, px, size,
 };
 use gpui_component::{ActiveTheme, VirtualListScrollHandle, scroll::Scrollbar, v_virtual_list};
-use iced_x86::{SpecializedFormatter, SpecializedFormatterTraitOptions};
+use iced_x86::{Decoder, DecoderOptions, SpecializedFormatter, SpecializedFormatterTraitOptions};
+use pe_parse::PEHeader;
+
+use crate::{
+    InfoDisplayPage, Route, RustDump,
+    loader::LoadedFile,
+    pe::{self, sections},
+};
 
-use crate::{Route, RustDump};
-use rd_core;
+/// Instructions decoded between checks for a cancelled load
+const CANCEL_CHECK_INTERVAL: usize = 0x10000;
 
-struct TraitOptions;
+pub(crate) struct TraitOptions;
 impl SpecializedFormatterTraitOptions for TraitOptions {
     const ENABLE_DB_DW_DD_DQ: bool = false;
 }
 
-type CustomFormatter = SpecializedFormatter<TraitOptions>;
+pub(crate) type CustomFormatter = SpecializedFormatter<TraitOptions>;
+
+/// Formatted instructions and their list item sizes, built by the loader off the UI thread
+#[derive(Default)]
+pub struct Disassembly {
+    pub addresses: Vec<u64>,
+    pub lines: Vec<SharedString>,
+    pub item_sizes: Vec<Size<Pixels>>,
+}
+
+impl Disassembly {
+    /// Decodes every code section of `bytes`, giving up with `None` once
+    /// `cancelled` is set
+    pub fn decode(bytes: &[u8], pe_header: &PEHeader, cancelled: &AtomicBool) -> Option<Self> {
+        let bitness = if pe::is_pe64(pe_header) { 64 } else { 32 };
+        let mut formatter = CustomFormatter::new();
+        let mut disassembly = Self::default();
+        for (va, code) in sections::code_sections(bytes, pe_header) {
+            let mut decoder = Decoder::with_ip(bitness, code, va, DecoderOptions::NONE);
+            for (i, instr) in decoder.iter().enumerate() {
+                if i % CANCEL_CHECK_INTERVAL == 0 && cancelled.load(Ordering::Relaxed) {
+                    return None;
+                }
+                let mut output = String::new();
+                formatter.format(&instr, &mut output);
+                disassembly.addresses.push(instr.ip());
+                disassembly
+                    .item_sizes
+                    .push(size(px(16. * output.len() as f32), px(22.)));
+                disassembly.lines.push(SharedString::from(output));
+            }
+        }
+        Some(disassembly)
+    }
+}
 
 pub struct Assembly {
     data: Rc<Vec<SharedString>>,
+    addresses: Vec<u64>,
+    selected: Option<usize>,
     item_sizes: Rc<Vec<Size<Pixels>>>,
     scroll_handle: VirtualListScrollHandle,
 }
@@ -26,34 +74,43 @@ impl Assembly {
     pub fn new(window: &mut Window, cx: &mut Context<RustDump>) -> Self {
         Self {
             data: Rc::new(vec![]),
+            addresses: vec![],
+            selected: None,
             scroll_handle: VirtualListScrollHandle::new(),
             item_sizes: Rc::new(vec![]),
         }
     }
 
-    pub fn load_file(&mut self, path: &Path, cx: &mut Context<RustDump>, window: &mut Window) {
-        println!("loading assembly");
-        let instructions = rd_core::load_assembly(path);
-        let mut formatter = CustomFormatter::new();
-        let mut item_sizes = vec![];
-
-        let mut out_vec = vec![];
-        for instr in instructions {
-            let mut output = String::new();
-            output.clear();
-            formatter.format(&instr, &mut output);
+    pub fn load_file(
+        &mut self,
+        file: &mut LoadedFile,
+        cx: &mut Context<RustDump>,
+        window: &mut Window,
+    ) -> anyhow::Result<()> {
+        // Only PE images can be disassembled
+        file.pe_header()?;
+        let disassembly = std::mem::take(&mut file.disassembly);
 
-            item_sizes.push(size(px(16. * output.len() as f32), px(22.)));
+        self.item_sizes = Rc::new(disassembly.item_sizes);
+        self.data = Rc::new(disassembly.lines);
+        self.addresses = disassembly.addresses;
+        self.selected = None;
+        Ok(())
+    }
 
-            let line = SharedString::new(output);
-            out_vec.push(line);
+    /// Scrolls to the instruction containing `va` and highlights it
+    pub fn go_to_address(&mut self, va: u64) {
+        let ix = self.addresses.partition_point(|addr| *addr <= va);
+        if ix == 0 {
+            return;
         }
-        self.item_sizes = Rc::new(item_sizes);
-
-        self.data = Rc::new(out_vec);
+        self.selected = Some(ix - 1);
+        self.scroll_handle
+            .scroll_to_item(ix - 1, ScrollStrategy::Top);
     }
     pub fn render_route(&self, cx: &Context<RustDump>) -> AnyElement {
         let data = self.data.clone();
+        let selected = self.selected;
         div()
             .font_family(SharedString::from("Diodrum Cyrillic"))
             .size_full()
@@ -68,6 +125,7 @@ impl Assembly {
                         visible_range
                             .map(|ix| {
                                 div()
+                                    .when(selected == Some(ix), |s| s.bg(cx.theme().accent))
                                     .child(data[ix].clone())
                                     .hover(|s| s.bg(cx.theme().info_hover).h(gpui::px(22.)))
                             })
@@ -108,7 +166,24 @@ impl Route for Assembly {
         self.render_route(cx)
     }
 
-    fn load(&mut self, cx: &mut Context<RustDump>, window: &mut Window, path: &std::path::Path) {
-        self.load_file(path, cx, window);
+    fn load(
+        &mut self,
+        cx: &mut Context<RustDump>,
+        window: &mut Window,
+        file: &mut LoadedFile,
+    ) -> anyhow::Result<()> {
+        self.load_file(file, cx, window)
+    }
+
+    fn go_to_address(&mut self, va: u64) {
+        Assembly::go_to_address(self, va);
+    }
+
+    fn scroll_offset(&self, _page: &InfoDisplayPage, _cx: &App) -> f32 {
+        f32::from(self.scroll_handle.offset().y)
+    }
+
+    fn set_scroll_offset(&mut self, offset: f32, _page: &InfoDisplayPage, _cx: &mut App) {
+        self.scroll_handle.set_offset(point(px(0.), px(offset)));
     }
 }
@@ -1,47 +1,65 @@
-use std::time::Instant;
-use std::{path::Path, rc::Rc};
+use std::sync::Arc;
 
 use gpui::{AnyElement, App, Context, Entity, SharedString, Window, div, prelude::*};
 use gpui_component::{
-    ActiveTheme,
+    ActiveTheme, StyledExt,
     table::{Column, Table, TableDelegate, TableState},
 };
-use rd_core::hex_string;
+use rd_core::push_hex;
 
-use crate::{Route, RustDump};
+use crate::{
+    InfoDisplayPage, Route, RustDump,
+    components::datatable::{set_table_scroll_offset, table_scroll_offset},
+    loader::LoadedFile,
+};
+
+/// Bytes shown per table row
+pub(crate) const ROW_LEN: usize = 16;
 
 pub struct Hexdump {
-    dump: Rc<Vec<Hexrow>>,
+    bytes: Arc<Vec<u8>>,
     table_delegate: HexDelegate,
     table_state: Entity<TableState<HexDelegate>>,
 }
 
-#[derive(Clone)]
-pub struct Hexrow {
-    pub offset: String,
-    pub bytes: String,
-    pub ascii: String,
-}
-
+/// Formats each row from the file bytes as it is rendered, so only the
+/// visible rows are ever turned into strings
 #[derive(Clone)]
 struct HexDelegate {
-    data: Rc<Vec<Hexrow>>,
+    bytes: Arc<Vec<u8>>,
     columns: Vec<Column>,
 }
 
+/// Space separated hex of a dump row
+pub(crate) fn row_hex(row: &[u8]) -> String {
+    let mut hex = String::with_capacity(row.len() * 3);
+    for (i, b) in row.iter().enumerate() {
+        if i != 0 {
+            hex.push(' ');
+        }
+        push_hex(&mut hex, *b);
+    }
+    hex
+}
+
+/// Printable ASCII of a dump row, with a dot for every other byte
+pub(crate) fn row_ascii(row: &[u8]) -> String {
+    row.iter()
+        .map(|b| {
+            if b.is_ascii_graphic() {
+                *b as char
+            } else {
+                '.'
+            }
+        })
+        .collect()
+}
+
 impl HexDelegate {
-    fn new(data: Vec<rd_core::OutputRow>, window: &mut Window) -> Self {
+    fn new(bytes: Arc<Vec<u8>>, window: &mut Window) -> Self {
         let col_w = window.bounds().size.width.to_f64() / 3.;
-        let data = data
-            .iter()
-            .map(|row| Hexrow {
-                offset: hex_string(&row.offset.to_le_bytes()),
-                bytes: row.ascii.clone(),
-                ascii: row.bytes_string(),
-            })
-            .collect();
         Self {
-            data: Rc::new(data),
+            bytes,
             columns: vec![
                 Column::new("offset", "Offset")
                     .width(col_w)
@@ -59,7 +77,7 @@ impl TableDelegate for HexDelegate {
     }
 
     fn rows_count(&self, _: &App) -> usize {
-        self.data.len()
+        self.bytes.len().div_ceil(ROW_LEN)
     }
 
     fn column(&self, col_ix: usize, _: &App) -> &Column {
@@ -73,13 +91,14 @@ impl TableDelegate for HexDelegate {
         _: &mut Window,
         _: &mut Context<TableState<Self>>,
     ) -> impl IntoElement {
-        let row = &self.data[row_ix];
+        let offset = row_ix * ROW_LEN;
+        let row = &self.bytes[offset..(offset + ROW_LEN).min(self.bytes.len())];
         let col = &self.columns[col_ix];
 
         match col.key.as_ref() {
-            "offset" => div().child(row.offset.clone()),
-            "hex" => div().child(row.ascii.clone()),
-            "ascii" => div().child(row.ascii.clone()),
+            "offset" => div().child(format!("{:08X}", offset)),
+            "hex" => div().child(row_hex(row)),
+            "ascii" => div().child(row_ascii(row)),
             _ => div().child("".to_string()),
         }
     }
@@ -87,41 +106,86 @@ impl TableDelegate for HexDelegate {
 
 impl Hexdump {
     pub fn new(window: &mut Window, cx: &mut Context<RustDump>) -> Self {
+        let delegate = HexDelegate::new(Arc::new(vec![]), window);
         Self {
-            dump: Rc::new(Vec::new()),
-            table_delegate: HexDelegate::new(vec![], window),
-            table_state: cx.new(|cx| TableState::new(HexDelegate::new(vec![], window), window, cx)),
+            bytes: delegate.bytes.clone(),
+            table_delegate: delegate.clone(),
+            table_state: cx.new(|cx| TableState::new(delegate, window, cx)),
         }
     }
 
-    pub fn load_file(&mut self, path: &Path, cx: &mut Context<RustDump>, window: &mut Window) {
-        let start = Instant::now();
-        let mut dump = vec![];
-        let _ = rd_core::create_dump(path.to_path_buf(), &mut dump);
-        println!("Dump time: {}", start.elapsed().as_secs_f64());
-        let delegate = HexDelegate::new(dump.clone(), window);
+    pub fn load_file(
+        &mut self,
+        file: &mut LoadedFile,
+        cx: &mut Context<RustDump>,
+        window: &mut Window,
+    ) -> anyhow::Result<()> {
+        let delegate = HexDelegate::new(file.bytes.clone(), window);
         let state = cx.new(|cx| TableState::new(delegate.clone(), window, cx));
 
+        self.bytes = delegate.bytes.clone();
         self.table_delegate = delegate;
         self.table_state = state;
+        Ok(())
     }
-    pub fn render_route(&self, cx: &Context<RustDump>) -> AnyElement {
+    pub fn render_route(&self, cx: &mut Context<RustDump>, app: &RustDump) -> AnyElement {
+        let search = &app.tab().search;
         div()
+            .v_flex()
             .font_family(SharedString::from("Diodrum Cyrillic"))
             .size_full()
             .bg(cx.theme().background)
             .text_color(cx.theme().foreground)
-            .child(Table::new(&self.table_state).bordered(false).stripe(false))
+            .child(search.render(cx))
+            .child(
+                div()
+                    .h_flex()
+                    .flex_1()
+                    .min_h_0()
+                    .w_full()
+                    .child(
+                        div()
+                            .flex_1()
+                            .h_full()
+                            .child(Table::new(&self.table_state).bordered(false).stripe(false)),
+                    )
+                    .children(search.render_results()),
+            )
             .into_any_element()
     }
 }
 
 impl Route for Hexdump {
-    fn render(&self, cx: &mut Context<RustDump>, _app: &RustDump) -> AnyElement {
-        self.render_route(cx)
+    fn render(&self, cx: &mut Context<RustDump>, app: &RustDump) -> AnyElement {
+        self.render_route(cx, app)
+    }
+
+    fn load(
+        &mut self,
+        cx: &mut Context<RustDump>,
+        window: &mut Window,
+        file: &mut LoadedFile,
+    ) -> anyhow::Result<()> {
+        self.load_file(file, cx, window)
+    }
+
+    /// Selects and scrolls to the row containing `offset`
+    fn go_to_offset(&mut self, offset: usize, cx: &mut App) {
+        if offset >= self.bytes.len() {
+            return;
+        }
+        let ix = offset / ROW_LEN;
+        self.table_state.update(cx, |state, cx| {
+            state.set_selected_row(ix, cx);
+            state.scroll_to_row(ix, cx);
+        });
+    }
+
+    fn scroll_offset(&self, _page: &InfoDisplayPage, cx: &App) -> f32 {
+        table_scroll_offset(&self.table_state, cx)
     }
 
-    fn load(&mut self, cx: &mut Context<RustDump>, window: &mut Window, path: &std::path::Path) {
-        self.load_file(path, cx, window);
+    fn set_scroll_offset(&mut self, offset: f32, _page: &InfoDisplayPage, cx: &mut App) {
+        set_table_scroll_offset(&self.table_state, offset, cx);
     }
 }
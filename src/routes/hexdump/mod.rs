@@ -1 +1,2 @@
 pub mod hexdump;
+pub mod search;
@@ -0,0 +1,445 @@
+use std::{path::PathBuf, sync::Arc};
+
+use anyhow::{anyhow, bail};
+use gpui::{
+    AppContext, Context, Entity, IntoElement, ParentElement, Styled, Subscription, Task, Window,
+    div,
+};
+use gpui_component::{
+    ActiveTheme, Selectable, StyledExt, WindowExt,
+    button::Button,
+    input::{Input, InputEvent, InputState},
+    notification::Notification,
+    table::Column,
+};
+use regex::bytes::{Regex, RegexBuilder};
+
+use crate::{Find, FindNext, FindPrevious, RouteName, RustDump, components::datatable::DataTable};
+
+/// Stops a search for a very common pattern from building a huge results list
+const MAX_MATCHES: usize = 10_000;
+/// Bytes of each match shown in the results list
+const PREVIEW_LEN: usize = 16;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum SearchKind {
+    Hex,
+    Ascii,
+    Utf16,
+    Regex,
+}
+
+pub struct SearchMatch {
+    pub offset: usize,
+    pub len: usize,
+}
+
+/// Matches of a background search with their results list rows
+struct Found {
+    matches: Vec<SearchMatch>,
+    truncated: bool,
+    rows: Vec<Vec<String>>,
+}
+
+/// Search bar and results of the Hexdump route, one per tab
+pub struct HexSearch {
+    pub input: Entity<InputState>,
+    kind: SearchKind,
+    bytes: Arc<Vec<u8>>,
+    /// Query and kind the matches were found with, Enter steps through them while unchanged
+    searched: Option<(String, SearchKind)>,
+    /// Query and kind of the search running in the background, dropping the task cancels it
+    searching: Option<((String, SearchKind), Task<()>)>,
+    matches: Vec<SearchMatch>,
+    truncated: bool,
+    current: Option<usize>,
+    results: DataTable,
+    _subscription: Subscription,
+}
+
+/// Hex bytes with optional whitespace, `??` matches any byte
+fn hex_pattern(query: &str) -> anyhow::Result<String> {
+    let digits: Vec<char> = query.chars().filter(|c| !c.is_whitespace()).collect();
+    if !digits.len().is_multiple_of(2) {
+        bail!("hex pattern has an odd number of digits");
+    }
+
+    let mut pattern = String::new();
+    for pair in digits.chunks(2) {
+        let pair: String = pair.iter().collect();
+        if pair == "??" {
+            pattern.push('.');
+        } else {
+            let byte = u8::from_str_radix(&pair, 16)
+                .map_err(|_| anyhow!("\"{}\" is not a hex byte", pair))?;
+            pattern.push_str(&format!("\\x{:02X}", byte));
+        }
+    }
+    Ok(pattern)
+}
+
+fn literal_pattern(bytes: impl IntoIterator<Item = u8>) -> String {
+    bytes
+        .into_iter()
+        .map(|byte| format!("\\x{:02X}", byte))
+        .collect()
+}
+
+/// Every kind of query becomes a byte regex, literals are escaped byte by byte
+pub fn compile(query: &str, kind: SearchKind) -> anyhow::Result<Regex> {
+    let pattern = match kind {
+        SearchKind::Hex => hex_pattern(query)?,
+        SearchKind::Ascii => literal_pattern(query.bytes()),
+        SearchKind::Utf16 => literal_pattern(query.encode_utf16().flat_map(u16::to_le_bytes)),
+        SearchKind::Regex => query.to_string(),
+    };
+    // Without Unicode `.` and `\xFF` match single bytes instead of UTF-8 sequences,
+    // and 0x0A is just another byte in a binary file
+    RegexBuilder::new(&pattern)
+        .unicode(false)
+        .dot_matches_new_line(true)
+        .build()
+        .map_err(|err| anyhow!("{}", err))
+}
+
+/// Non-overlapping matches, and whether the search stopped at `MAX_MATCHES`
+pub fn find_all(bytes: &[u8], regex: &Regex) -> (Vec<SearchMatch>, bool) {
+    let mut matches: Vec<SearchMatch> = regex
+        .find_iter(bytes)
+        .filter(|found| !found.is_empty())
+        .take(MAX_MATCHES + 1)
+        .map(|found| SearchMatch {
+            offset: found.start(),
+            len: found.len(),
+        })
+        .collect();
+    let truncated = matches.len() > MAX_MATCHES;
+    matches.truncate(MAX_MATCHES);
+    (matches, truncated)
+}
+
+fn columns() -> Vec<Column> {
+    vec![
+        Column::new("offset", "Offset").resizable(true).width(100.),
+        Column::new("length", "Length").resizable(true).width(60.),
+        Column::new("bytes", "Bytes").resizable(true).width(300.),
+    ]
+}
+
+impl HexSearch {
+    pub fn new(window: &mut Window, cx: &mut Context<RustDump>) -> Self {
+        let input = cx.new(|cx| InputState::new(window, cx).placeholder("Search"));
+        let subscription = cx.subscribe_in(
+            &input,
+            window,
+            |app, _input, event: &InputEvent, window, cx| {
+                if let InputEvent::PressEnter { .. } = event {
+                    app.search(window, cx);
+                }
+            },
+        );
+
+        let results = DataTable::new(columns(), window, cx);
+        results.on_row_select(cx, |app, row, cx| app.show_search_result(row, cx));
+
+        Self {
+            input,
+            kind: SearchKind::Hex,
+            bytes: Arc::new(vec![]),
+            searched: None,
+            searching: None,
+            matches: vec![],
+            truncated: false,
+            current: None,
+            results,
+            _subscription: subscription,
+        }
+    }
+
+    pub fn load(&mut self, bytes: Arc<Vec<u8>>) {
+        self.bytes = bytes;
+    }
+
+    fn status(&self) -> String {
+        if self.searching.is_some() {
+            return "Searching…".to_string();
+        }
+        match (self.current, self.matches.len()) {
+            _ if self.searched.is_none() => String::new(),
+            (_, 0) => "No matches".to_string(),
+            (Some(ix), count) if self.truncated => format!("{} of {}+", ix + 1, count),
+            (Some(ix), count) => format!("{} of {}", ix + 1, count),
+            (None, count) if self.truncated => format!("{}+ matches", count),
+            (None, count) => format!("{} matches", count),
+        }
+    }
+
+    pub fn render(&self, cx: &mut Context<RustDump>) -> impl IntoElement {
+        let kind_button = |id: &'static str, label: &'static str, kind: SearchKind| {
+            Button::new(id)
+                .label(label)
+                .selected(self.kind == kind)
+                .on_click(cx.listener(move |app, _event, _window, cx| {
+                    app.tab_mut().search.kind = kind;
+                    cx.notify();
+                }))
+        };
+
+        div()
+            .h_flex()
+            .gap_2()
+            .p_2()
+            .border_b_1()
+            .border_color(cx.theme().sidebar_border)
+            .child(div().w_64().child(Input::new(&self.input)))
+            .child(kind_button("search_hex", "Hex", SearchKind::Hex))
+            .child(kind_button("search_ascii", "ASCII", SearchKind::Ascii))
+            .child(kind_button("search_utf16", "UTF-16LE", SearchKind::Utf16))
+            .child(kind_button("search_regex", "Regex", SearchKind::Regex))
+            .child(Button::new("search_prev").label("Previous").on_click(
+                cx.listener(|app, _event, window, cx| app.find_previous(&FindPrevious, window, cx)),
+            ))
+            .child(Button::new("search_next").label("Next").on_click(
+                cx.listener(|app, _event, window, cx| app.find_next(&FindNext, window, cx)),
+            ))
+            .child(self.status())
+    }
+
+    /// The results list, once a search found something
+    pub fn render_results(&self) -> Option<impl IntoElement> {
+        if self.matches.is_empty() {
+            return None;
+        }
+        Some(div().w_1_3().h_full().child(self.results.render()))
+    }
+}
+
+impl RustDump {
+    fn show_search_result(&mut self, ix: usize, cx: &mut Context<RustDump>) {
+        let tab = self.tab_mut();
+        let Some(found) = tab.search.matches.get(ix) else {
+            return;
+        };
+        let offset = found.offset;
+        tab.search.current = Some(ix);
+        if let Some(hexdump) = tab.routes.get_mut(&RouteName::Hexdump) {
+            hexdump.go_to_offset(offset, cx);
+        }
+        cx.notify();
+    }
+
+    /// Runs the query in the search bar on the background executor, or steps to the next
+    /// match if it already ran
+    pub fn search(&mut self, window: &mut Window, cx: &mut Context<RustDump>) {
+        let Some(tab) = self.tabs.get_mut(self.active_tab) else {
+            return;
+        };
+        let search = &mut tab.search;
+        let query = (search.input.read(cx).value().to_string(), search.kind);
+        if search.searched.as_ref() == Some(&query) {
+            self.find_next(&FindNext, window, cx);
+            return;
+        }
+        if search
+            .searching
+            .as_ref()
+            .is_some_and(|(searching, _)| *searching == query)
+        {
+            return;
+        }
+
+        search.matches.clear();
+        search.current = None;
+        search.searched = None;
+        search.searching = None;
+        if query.0.is_empty() {
+            cx.notify();
+            return;
+        }
+        let regex = match compile(&query.0, query.1) {
+            Ok(regex) => regex,
+            Err(err) => {
+                window.push_notification(Notification::error(format!("{:#}", err)), cx);
+                cx.notify();
+                return;
+            }
+        };
+
+        let path = tab.path.clone();
+        let bytes = search.bytes.clone();
+        let task = cx.spawn_in(window, {
+            let query = query.clone();
+            async move |this, cx| {
+                let found = cx
+                    .background_spawn(async move {
+                        let (matches, truncated) = find_all(&bytes, &regex);
+                        let rows = matches
+                            .iter()
+                            .map(|found| {
+                                let end = found.offset + found.len.min(PREVIEW_LEN);
+                                let preview = bytes[found.offset..end]
+                                    .iter()
+                                    .map(|byte| format!("{:02X}", byte))
+                                    .collect::<Vec<_>>()
+                                    .join(" ");
+                                vec![
+                                    format!("0x{:08X}", found.offset),
+                                    found.len.to_string(),
+                                    preview,
+                                ]
+                            })
+                            .collect();
+                        Found {
+                            matches,
+                            truncated,
+                            rows,
+                        }
+                    })
+                    .await;
+                let _ = this.update_in(cx, |app, window, cx| {
+                    app.finish_search(path, query, found, window, cx);
+                });
+            }
+        });
+        search.searching = Some((query, task));
+        cx.notify();
+    }
+
+    /// Hands the matches to the tab the search ran in, which may have closed meanwhile
+    fn finish_search(
+        &mut self,
+        path: PathBuf,
+        query: (String, SearchKind),
+        found: Found,
+        window: &mut Window,
+        cx: &mut Context<RustDump>,
+    ) {
+        let Some(ix) = self.tabs.iter().position(|tab| tab.path == path) else {
+            return;
+        };
+        let search = &mut self.tabs[ix].search;
+        search.searching = None;
+        search.results.set_rows(found.rows, cx);
+        search.matches = found.matches;
+        search.truncated = found.truncated;
+        search.searched = Some(query);
+
+        if found.truncated {
+            window.push_notification(
+                Notification::warning(format!("Showing the first {} matches", MAX_MATCHES)),
+                cx,
+            );
+        }
+        if ix == self.active_tab {
+            self.show_search_result(0, cx);
+        }
+        cx.notify();
+    }
+
+    pub fn find(&mut self, _: &Find, window: &mut Window, cx: &mut Context<RustDump>) {
+        let Some(tab) = self.tabs.get_mut(self.active_tab) else {
+            return;
+        };
+        if tab.route_errors.contains_key(&RouteName::Hexdump) {
+            return;
+        }
+        tab.current_route = RouteName::Hexdump;
+        tab.search
+            .input
+            .update(cx, |input, cx| input.focus(window, cx));
+        cx.notify();
+    }
+
+    pub fn find_next(&mut self, _: &FindNext, _window: &mut Window, cx: &mut Context<RustDump>) {
+        let Some(tab) = self.tabs.get(self.active_tab) else {
+            return;
+        };
+        let count = tab.search.matches.len();
+        if count == 0 {
+            return;
+        }
+        let ix = tab.search.current.map_or(0, |ix| (ix + 1) % count);
+        self.show_search_result(ix, cx);
+    }
+
+    pub fn find_previous(
+        &mut self,
+        _: &FindPrevious,
+        _window: &mut Window,
+        cx: &mut Context<RustDump>,
+    ) {
+        let Some(tab) = self.tabs.get(self.active_tab) else {
+            return;
+        };
+        let count = tab.search.matches.len();
+        if count == 0 {
+            return;
+        }
+        let ix = tab
+            .search
+            .current
+            .map_or(count - 1, |ix| (ix + count - 1) % count);
+        self.show_search_result(ix, cx);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn offsets(bytes: &[u8], query: &str, kind: SearchKind) -> Vec<usize> {
+        let regex = compile(query, kind).unwrap();
+        find_all(bytes, &regex)
+            .0
+            .iter()
+            .map(|found| found.offset)
+            .collect()
+    }
+
+    #[test]
+    fn hex_wildcards() {
+        let bytes = [0x4D, 0x5A, 0x90, 0x00, 0x4D, 0x0A, 0x90, 0x4D, 0x90];
+        assert_eq!(offsets(&bytes, "4D ?? 90", SearchKind::Hex), vec![0, 4]);
+        assert_eq!(offsets(&bytes, "4d5a", SearchKind::Hex), vec![0]);
+    }
+
+    #[test]
+    fn hex_rejects_malformed_patterns() {
+        assert!(compile("4D5", SearchKind::Hex).is_err());
+        assert!(compile("4D ?", SearchKind::Hex).is_err());
+        assert!(compile("ZZ", SearchKind::Hex).is_err());
+    }
+
+    #[test]
+    fn utf16_text() {
+        let bytes = b"xA\0B\0A\0B\0AB";
+        assert_eq!(offsets(bytes, "AB", SearchKind::Utf16), vec![1, 5]);
+        assert_eq!(offsets(bytes, "AB", SearchKind::Ascii), vec![9]);
+    }
+
+    #[test]
+    fn regex_dot_matches_newline_byte() {
+        assert_eq!(offsets(b"A\nB", "A.B", SearchKind::Regex), vec![0]);
+    }
+
+    #[test]
+    fn matches_do_not_overlap() {
+        let regex = compile("AA", SearchKind::Ascii).unwrap();
+        let (matches, truncated) = find_all(b"AAAAA", &regex);
+        assert!(!truncated);
+        let found: Vec<(usize, usize)> = matches.iter().map(|m| (m.offset, m.len)).collect();
+        assert_eq!(found, vec![(0, 2), (2, 2)]);
+    }
+
+    #[test]
+    fn truncates_at_max_matches() {
+        let regex = compile("00", SearchKind::Hex).unwrap();
+        let (matches, truncated) = find_all(&vec![0; MAX_MATCHES], &regex);
+        assert_eq!(matches.len(), MAX_MATCHES);
+        assert!(!truncated);
+
+        let (matches, truncated) = find_all(&vec![0; MAX_MATCHES + 1], &regex);
+        assert_eq!(matches.len(), MAX_MATCHES);
+        assert!(truncated);
+    }
+}
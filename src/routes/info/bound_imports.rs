@@ -0,0 +1,110 @@
+use gpui::{AnyElement, Context, IntoElement, ParentElement, Styled, Window, div};
+use gpui_component::{StyledExt, table::Column};
+
+use crate::{RustDump, components::datatable::DataTable, pe::bound_imports::BoundImport};
+
+pub struct BoundImportsView {
+    pub bound: Vec<BoundImport>,
+    missing: Vec<String>,
+    table: DataTable,
+}
+
+fn columns() -> Vec<Column> {
+    vec![
+        Column::new("offset", "Offset")
+            .sortable()
+            .resizable(true)
+            .width(100.),
+        Column::new("kind", "Kind").resizable(true).width(100.),
+        Column::new("module", "Module")
+            .sortable()
+            .resizable(true)
+            .width(200.),
+        Column::new("timestamp", "TimeDateStamp")
+            .sortable()
+            .resizable(true)
+            .width(125.),
+        Column::new("name_offset", "Name Offset")
+            .resizable(true)
+            .width(100.),
+        Column::new("forwarders", "Forwarder Refs")
+            .resizable(true)
+            .width(125.),
+        Column::new("status", "Status")
+            .sortable()
+            .resizable(true)
+            .width(250.),
+    ]
+}
+
+impl BoundImportsView {
+    pub fn new(window: &mut Window, cx: &mut Context<RustDump>) -> Self {
+        Self {
+            bound: vec![],
+            missing: vec![],
+            table: DataTable::new(columns(), window, cx),
+        }
+    }
+
+    pub fn load(
+        &mut self,
+        bound: Vec<BoundImport>,
+        missing: Vec<String>,
+        window: &mut Window,
+        cx: &mut Context<RustDump>,
+    ) {
+        let mut rows = vec![];
+        for desc in &bound {
+            rows.push(vec![
+                format!("0x{:08X}", desc.offset),
+                "Descriptor".to_string(),
+                desc.module_name.clone(),
+                format!("0x{:08X}", desc.time_date_stamp),
+                format!("0x{:04X}", desc.module_name_offset),
+                desc.forwarders.len().to_string(),
+                desc.status.description().to_string(),
+            ]);
+            // Forwarder refs are listed under the descriptor that owns them
+            rows.extend(desc.forwarders.iter().map(|fwd| {
+                vec![
+                    format!("0x{:08X}", fwd.offset),
+                    "Forwarder".to_string(),
+                    format!("  {}", fwd.module_name),
+                    format!("0x{:08X}", fwd.time_date_stamp),
+                    format!("0x{:04X}", fwd.module_name_offset),
+                    String::new(),
+                    String::new(),
+                ]
+            }));
+        }
+        self.table.load(rows, window, cx);
+
+        self.bound = bound;
+        self.missing = missing;
+    }
+
+    pub fn scroll_table(&self) -> &DataTable {
+        &self.table
+    }
+
+    pub fn render(&self) -> AnyElement {
+        if self.bound.is_empty() && self.missing.is_empty() {
+            return div()
+                .p_2()
+                .child("No bound import directory")
+                .into_any_element();
+        }
+
+        div()
+            .v_flex()
+            .size_full()
+            .children((!self.missing.is_empty()).then(|| {
+                div().p_2().child(format!(
+                    "Bound in the import directory without a bound descriptor: {}",
+                    self.missing.join(", ")
+                ))
+            }))
+            .child(div().size_full().child(self.table.render()))
+            .into_any_element()
+    }
+}
@@ -0,0 +1,304 @@
+use gpui::{AnyElement, Context, IntoElement, ParentElement, Styled, Window, div};
+use gpui_component::{StyledExt, table::Column};
+
+use crate::{
+    InfoDisplayPage, RustDump,
+    components::{
+        datatable::{DataTable, ScrollTable},
+        headertable::{HeaderData, HeaderTable},
+    },
+    pe::clr::{self, ClrInfo},
+};
+
+pub struct ClrView {
+    pub clr: Option<ClrInfo>,
+    /// Names of the decoded metadata tables, in sidebar order
+    pub table_names: Vec<&'static str>,
+    header_table: HeaderTable,
+    streams_table: DataTable,
+    guids_table: DataTable,
+    /// Built when the table's page is first opened
+    metadata_tables: Vec<Option<DataTable>>,
+    user_strings_table: DataTable,
+    user_strings_loaded: bool,
+}
+
+fn streams_columns() -> Vec<Column> {
+    vec![
+        Column::new("name", "Stream").resizable(true).width(100.),
+        Column::new("offset", "Offset").resizable(true).width(100.),
+        Column::new("size", "Size").resizable(true).width(100.),
+    ]
+}
+
+fn guids_columns() -> Vec<Column> {
+    vec![
+        Column::new("index", "Index").resizable(true).width(60.),
+        Column::new("guid", "GUID").resizable(true).width(300.),
+    ]
+}
+
+fn user_strings_columns() -> Vec<Column> {
+    vec![
+        Column::new("offset", "Offset")
+            .sortable()
+            .resizable(true)
+            .width(100.),
+        Column::new("string", "String")
+            .sortable()
+            .resizable(true)
+            .width(600.),
+    ]
+}
+
+fn metadata_columns(names: &[&'static str]) -> Vec<Column> {
+    names
+        .iter()
+        .map(|name| {
+            Column::new(*name, *name)
+                .sortable()
+                .resizable(true)
+                .width(if *name == "Signature" { 250. } else { 150. })
+        })
+        .collect()
+}
+
+impl ClrView {
+    pub fn new(window: &mut Window, cx: &mut Context<RustDump>) -> Self {
+        Self {
+            clr: None,
+            table_names: vec![],
+            header_table: HeaderTable::new(window, cx),
+            streams_table: DataTable::new(streams_columns(), window, cx),
+            guids_table: DataTable::new(guids_columns(), window, cx),
+            metadata_tables: vec![],
+            user_strings_table: DataTable::new(user_strings_columns(), window, cx),
+            user_strings_loaded: false,
+        }
+    }
+
+    pub fn load(&mut self, clr: Option<ClrInfo>, window: &mut Window, cx: &mut Context<RustDump>) {
+        let Some(info) = &clr else {
+            self.header_table.load(vec![], window, cx);
+            self.streams_table.load(vec![], window, cx);
+            self.guids_table.load(vec![], window, cx);
+            self.user_strings_table.load(vec![], window, cx);
+            self.metadata_tables = vec![];
+            self.table_names = vec![];
+            self.clr = None;
+            return;
+        };
+
+        let hdr = &info.header;
+        let rva_size = |rva: u32, size: u32| format!("0x{:08X} (0x{:X} bytes)", rva, size);
+        let mut data = vec![
+            HeaderData::new(
+                hdr.offset,
+                "cb",
+                format!("0x{:X}", hdr.cb),
+                "Size of header",
+            ),
+            HeaderData::new(
+                hdr.offset + 4,
+                "RuntimeVersion",
+                format!(
+                    "{}.{}",
+                    hdr.major_runtime_version, hdr.minor_runtime_version
+                ),
+                "Minimum runtime version",
+            ),
+            HeaderData::new(
+                hdr.offset + 8,
+                "MetaData",
+                rva_size(hdr.metadata_rva, hdr.metadata_size),
+                "Metadata root",
+            ),
+            HeaderData::new(
+                hdr.offset + 16,
+                "Flags",
+                format!("0x{:08X}", hdr.flags),
+                &clr::flag_names(hdr.flags).join(" | "),
+            ),
+            HeaderData::new(
+                hdr.offset + 20,
+                "EntryPointToken",
+                format!("0x{:08X}", hdr.entry_point),
+                "MethodDef token or native entry point RVA",
+            ),
+            HeaderData::new(
+                hdr.offset + 24,
+                "Resources",
+                rva_size(hdr.resources_rva, hdr.resources_size),
+                "Managed resources",
+            ),
+            HeaderData::new(
+                hdr.offset + 32,
+                "StrongNameSignature",
+                rva_size(hdr.strong_name_rva, hdr.strong_name_size),
+                "Strong name hash",
+            ),
+            HeaderData::new(
+                hdr.offset + 48,
+                "VTableFixups",
+                rva_size(hdr.vtable_fixups_rva, hdr.vtable_fixups_size),
+                "Mixed mode vtable fixups",
+            ),
+        ];
+
+        let mut streams = vec![];
+        let mut guids = vec![];
+        self.user_strings_loaded = false;
+        self.metadata_tables = vec![];
+        self.table_names = vec![];
+        if let Some(md) = &info.metadata {
+            data.push(HeaderData::new(
+                md.offset + 4,
+                "MetadataVersion",
+                format!("{}.{}", md.major_version, md.minor_version),
+                &md.version,
+            ));
+            if let (Some(tables_offset), Some((major, minor))) =
+                (md.tables_offset, md.tables_version)
+            {
+                data.push(HeaderData::new(
+                    tables_offset + 4,
+                    "TablesVersion",
+                    format!("{}.{}", major, minor),
+                    &format!("HeapSizes 0x{:02X}", md.heap_sizes),
+                ));
+                data.push(HeaderData::new(
+                    tables_offset + 8,
+                    "Valid",
+                    format!("0x{:016X}", md.valid),
+                    &format!("{} tables", md.tables.len()),
+                ));
+            }
+
+            streams = md
+                .streams
+                .iter()
+                .map(|stream| {
+                    vec![
+                        stream.name.clone(),
+                        format!("0x{:08X}", stream.offset),
+                        format!("0x{:X}", stream.size),
+                    ]
+                })
+                .collect();
+            // #GUID heap indexes start at 1
+            guids = md
+                .guids
+                .iter()
+                .enumerate()
+                .map(|(i, guid)| vec![(i + 1).to_string(), guid.clone()])
+                .collect();
+            for table in &md.tables {
+                self.metadata_tables.push(None);
+                self.table_names.push(table.name);
+            }
+        }
+        self.header_table.load(data, window, cx);
+        self.streams_table.load(streams, window, cx);
+        self.guids_table.load(guids, window, cx);
+
+        self.clr = clr;
+    }
+
+    /// Builds the table of a metadata table or user strings page if it is not built yet
+    pub fn open(
+        &mut self,
+        page: &InfoDisplayPage,
+        window: &mut Window,
+        cx: &mut Context<RustDump>,
+    ) {
+        let Some(md) = self.clr.as_ref().and_then(|info| info.metadata.as_ref()) else {
+            return;
+        };
+        match page {
+            InfoDisplayPage::ClrTable(idx) => {
+                let slot = self.metadata_tables.get_mut(*idx);
+                let (Some(table), Some(slot)) = (md.tables.get(*idx), slot) else {
+                    return;
+                };
+                if slot.is_some() {
+                    return;
+                }
+                let mut view = DataTable::new(metadata_columns(&table.columns), window, cx);
+                view.load(table.rows.clone(), window, cx);
+                *slot = Some(view);
+            }
+            InfoDisplayPage::ClrUserStrings if !self.user_strings_loaded => {
+                let rows = md
+                    .user_strings
+                    .iter()
+                    .map(|(offset, string)| vec![format!("0x{:06X}", offset), string.clone()])
+                    .collect();
+                self.user_strings_table.load(rows, window, cx);
+                self.user_strings_loaded = true;
+            }
+            _ => {}
+        }
+    }
+
+    /// Main table of a CLR page, `None` for other pages
+    pub fn scroll_table(&self, page: &InfoDisplayPage) -> Option<&dyn ScrollTable> {
+        match page {
+            InfoDisplayPage::Clr => Some(&self.header_table),
+            InfoDisplayPage::ClrTable(idx) => self
+                .metadata_tables
+                .get(*idx)?
+                .as_ref()
+                .map(|table| table as &dyn ScrollTable),
+            InfoDisplayPage::ClrUserStrings => Some(&self.user_strings_table),
+            _ => None,
+        }
+    }
+
+    pub fn render_overview(&self) -> AnyElement {
+        if self.clr.is_none() {
+            return div()
+                .p_2()
+                .child("No CLR runtime header")
+                .into_any_element();
+        }
+
+        div()
+            .grid()
+            .grid_cols(2)
+            .grid_rows(1)
+            .size_full()
+            .child(self.header_table.render())
+            .child(
+                div()
+                    .v_flex()
+                    .size_full()
+                    .child(div().flex_1().child(self.streams_table.render()))
+                    .child(div().p_2().child("#GUID heap"))
+                    .child(div().flex_1().child(self.guids_table.render())),
+            )
+            .into_any_element()
+    }
+
+    pub fn render_table(&self, idx: usize) -> AnyElement {
+        let (Some(name), Some(table)) = (
+            self.table_names.get(idx),
+            self.metadata_tables.get(idx).and_then(Option::as_ref),
+        ) else {
+            return div().into_any_element();
+        };
+
+        div()
+            .v_flex()
+            .size_full()
+            .child(div().p_2().child(name.to_string()))
+            .child(div().size_full().child(table.render()))
+            .into_any_element()
+    }
+
+    pub fn render_user_strings(&self) -> AnyElement {
+        div()
+            .size_full()
+            .child(self.user_strings_table.render())
+            .into_any_element()
+    }
+}
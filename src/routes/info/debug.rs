@@ -0,0 +1,167 @@
+use gpui::{AnyElement, Context, IntoElement, ParentElement, Styled, Window, div};
+use gpui_component::table::Column;
+
+use crate::{
+    RustDump,
+    components::datatable::DataTable,
+    pe::debug::{self, DebugEntry, DebugInfo},
+};
+
+pub struct DebugView {
+    pub entries: Vec<DebugEntry>,
+    entry_table: DataTable,
+    details_table: DataTable,
+}
+
+fn entry_columns() -> Vec<Column> {
+    vec![
+        Column::new("offset", "Offset").resizable(true).width(100.),
+        Column::new("type", "Type").resizable(true).width(175.),
+        Column::new("timestamp", "TimeDateStamp")
+            .resizable(true)
+            .width(125.),
+        Column::new("version", "Version").resizable(true).width(75.),
+        Column::new("size", "SizeOfData")
+            .resizable(true)
+            .width(100.),
+        Column::new("rva", "AddressOfRawData")
+            .resizable(true)
+            .width(150.),
+        Column::new("ptr", "PointerToRawData")
+            .resizable(true)
+            .width(150.),
+    ]
+}
+
+fn details_columns() -> Vec<Column> {
+    vec![
+        Column::new("entry", "Entry").resizable(true).width(175.),
+        Column::new("field", "Field").resizable(true).width(175.),
+        Column::new("value", "Value").resizable(true).width(500.),
+    ]
+}
+
+fn detail_rows(entry: &DebugEntry) -> Vec<(String, String)> {
+    match &entry.info {
+        DebugInfo::CodeViewRsds {
+            guid,
+            age,
+            pdb_path,
+        } => vec![
+            ("Format".to_string(), "RSDS".to_string()),
+            ("GUID".to_string(), guid.clone()),
+            ("Age".to_string(), age.to_string()),
+            ("PDB".to_string(), pdb_path.clone()),
+        ],
+        DebugInfo::CodeViewNb10 {
+            offset,
+            signature,
+            age,
+            pdb_path,
+        } => vec![
+            ("Format".to_string(), "NB10".to_string()),
+            ("Offset".to_string(), format!("0x{:08X}", offset)),
+            ("Signature".to_string(), format!("0x{:08X}", signature)),
+            ("Age".to_string(), age.to_string()),
+            ("PDB".to_string(), pdb_path.clone()),
+        ],
+        DebugInfo::Pogo { signature, entries } => {
+            let mut rows = vec![("Signature".to_string(), signature.clone())];
+            rows.extend(entries.iter().map(|(rva, size, name)| {
+                (
+                    name.clone(),
+                    format!("RVA 0x{:08X}, size 0x{:X}", rva, size),
+                )
+            }));
+            rows
+        }
+        DebugInfo::Repro { hash } if hash.is_empty() => {
+            vec![("Hash".to_string(), "deterministic, no hash".to_string())]
+        }
+        DebugInfo::Repro { hash } => vec![("Hash".to_string(), hash.clone())],
+        DebugInfo::VcFeature {
+            pre_vc11,
+            c_cpp,
+            gs,
+            sdl,
+            guard_n,
+        } => vec![
+            ("Pre-VC++ 11.00".to_string(), pre_vc11.to_string()),
+            ("C/C++".to_string(), c_cpp.to_string()),
+            ("/GS".to_string(), gs.to_string()),
+            ("/sdl".to_string(), sdl.to_string()),
+            ("guardN".to_string(), guard_n.to_string()),
+        ],
+        DebugInfo::ExDllCharacteristics { flags, names } => vec![
+            ("Flags".to_string(), format!("0x{:08X}", flags)),
+            ("Set".to_string(), names.join(" | ")),
+        ],
+        DebugInfo::None => vec![],
+    }
+}
+
+impl DebugView {
+    pub fn new(window: &mut Window, cx: &mut Context<RustDump>) -> Self {
+        Self {
+            entries: vec![],
+            entry_table: DataTable::new(entry_columns(), window, cx),
+            details_table: DataTable::new(details_columns(), window, cx),
+        }
+    }
+
+    pub fn load(
+        &mut self,
+        entries: Vec<DebugEntry>,
+        window: &mut Window,
+        cx: &mut Context<RustDump>,
+    ) {
+        let rows = entries
+            .iter()
+            .map(|entry| {
+                vec![
+                    format!("0x{:08X}", entry.offset),
+                    debug::type_name(entry.kind).to_string(),
+                    format!("0x{:08X}", entry.time_date_stamp),
+                    format!("{}.{}", entry.major_version, entry.minor_version),
+                    format!("0x{:X}", entry.size_of_data),
+                    format!("0x{:08X}", entry.address_of_raw_data),
+                    format!("0x{:08X}", entry.pointer_to_raw_data),
+                ]
+            })
+            .collect();
+        self.entry_table.load(rows, window, cx);
+
+        let details = entries
+            .iter()
+            .enumerate()
+            .flat_map(|(i, entry)| {
+                let label = format!("[{}] {}", i, debug::type_name(entry.kind));
+                detail_rows(entry)
+                    .into_iter()
+                    .map(move |(field, value)| vec![label.clone(), field, value])
+            })
+            .collect();
+        self.details_table.load(details, window, cx);
+
+        self.entries = entries;
+    }
+
+    pub fn scroll_table(&self) -> &DataTable {
+        &self.entry_table
+    }
+
+    pub fn render(&self) -> AnyElement {
+        if self.entries.is_empty() {
+            return div().p_2().child("No debug directory").into_any_element();
+        }
+
+        div()
+            .grid()
+            .grid_cols(1)
+            .grid_rows(2)
+            .size_full()
+            .child(self.entry_table.render())
+            .child(self.details_table.render())
+            .into_any_element()
+    }
+}
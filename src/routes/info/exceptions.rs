@@ -0,0 +1,144 @@
+use gpui::{AnyElement, Context, IntoElement, ParentElement, Styled, Window, div};
+use gpui_component::table::Column;
+
+use crate::{
+    RouteName, RustDump,
+    components::datatable::DataTable,
+    pe::{
+        address::AddressSpace,
+        exception::{self, RuntimeFunction},
+    },
+};
+
+pub struct ExceptionsView {
+    pub functions: Vec<RuntimeFunction>,
+    table: DataTable,
+}
+
+fn columns() -> Vec<Column> {
+    vec![
+        Column::new("begin", "Begin")
+            .sortable()
+            .resizable(true)
+            .width(100.),
+        Column::new("end", "End")
+            .sortable()
+            .resizable(true)
+            .width(100.),
+        Column::new("unwind", "Unwind Info")
+            .sortable()
+            .resizable(true)
+            .width(100.),
+        Column::new("version", "Ver").resizable(true).width(50.),
+        Column::new("flags", "Flags").resizable(true).width(125.),
+        Column::new("prolog", "Prolog").resizable(true).width(60.),
+        Column::new("frame", "Frame").resizable(true).width(100.),
+        Column::new("codes", "Unwind Codes")
+            .resizable(true)
+            .width(500.),
+        Column::new("extra", "Handler / Chained")
+            .resizable(true)
+            .width(250.),
+    ]
+}
+
+impl ExceptionsView {
+    pub fn new(window: &mut Window, cx: &mut Context<RustDump>) -> Self {
+        Self {
+            functions: vec![],
+            table: DataTable::new(columns(), window, cx),
+        }
+    }
+
+    pub fn load(
+        &mut self,
+        functions: Vec<RuntimeFunction>,
+        space: &AddressSpace,
+        window: &mut Window,
+        cx: &mut Context<RustDump>,
+    ) {
+        let rows = functions
+            .iter()
+            .map(|func| {
+                let mut row = vec![
+                    format!("0x{:08X}", func.begin_address),
+                    format!("0x{:08X}", func.end_address),
+                    format!("0x{:08X}", func.unwind_info_address),
+                ];
+                let Some(info) = &func.unwind_info else {
+                    row.extend(std::iter::repeat_n(String::new(), 6));
+                    return row;
+                };
+
+                let frame = if info.frame_register != 0 {
+                    format!(
+                        "{}+0x{:X}",
+                        exception::register_name(info.frame_register),
+                        info.frame_offset as u32 * 16
+                    )
+                } else {
+                    String::new()
+                };
+                let codes = info
+                    .codes
+                    .iter()
+                    .map(|code| format!("{:02X}: {}", code.code_offset, code.description))
+                    .collect::<Vec<String>>()
+                    .join("; ");
+                let extra = match (info.handler, info.chained) {
+                    (_, Some((begin, end, unwind))) => {
+                        format!("chained 0x{:08X}-0x{:08X} (0x{:08X})", begin, end, unwind)
+                    }
+                    (Some(handler), None) => format!("handler 0x{:08X}", handler),
+                    (None, None) => String::new(),
+                };
+
+                row.extend([
+                    info.version.to_string(),
+                    exception::flag_names(info.flags).join(" | "),
+                    format!("0x{:X}", info.size_of_prolog),
+                    frame,
+                    codes,
+                    extra,
+                ]);
+                row
+            })
+            .collect();
+        self.table.load(rows, window, cx);
+
+        // Selecting a function shows its prologue in the disassembly
+        let starts: Vec<u64> = functions
+            .iter()
+            .map(|func| space.rva_to_va(func.begin_address))
+            .collect();
+        self.table.on_row_select(cx, move |app, row, cx| {
+            let Some(va) = starts.get(row) else { return };
+            let tab = app.tab_mut();
+            if let Some(assembly) = tab.routes.get_mut(&RouteName::Assembly) {
+                assembly.go_to_address(*va);
+            }
+            tab.current_route = RouteName::Assembly;
+            cx.notify();
+        });
+
+        self.functions = functions;
+    }
+
+    pub fn scroll_table(&self) -> &DataTable {
+        &self.table
+    }
+
+    pub fn render(&self) -> AnyElement {
+        if self.functions.is_empty() {
+            return div()
+                .p_2()
+                .child("No x64 exception directory")
+                .into_any_element();
+        }
+
+        div()
+            .size_full()
+            .child(self.table.render())
+            .into_any_element()
+    }
+}
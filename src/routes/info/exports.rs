@@ -0,0 +1,143 @@
+use gpui::{AnyElement, Context, IntoElement, ParentElement, Styled, Window, div};
+use gpui_component::{StyledExt, table::Column};
+
+use crate::{
+    RustDump,
+    components::{
+        datatable::DataTable,
+        headertable::{HeaderData, HeaderTable},
+    },
+    pe::exports::ExportDirectory,
+};
+
+pub struct ExportsView {
+    pub directory: Option<ExportDirectory>,
+    directory_table: HeaderTable,
+    exports_table: DataTable,
+}
+
+fn export_columns() -> Vec<Column> {
+    vec![
+        Column::new("ordinal", "Ordinal")
+            .sortable()
+            .resizable(true)
+            .width(75.),
+        Column::new("rva", "RVA")
+            .sortable()
+            .resizable(true)
+            .width(100.),
+        Column::new("name", "Name")
+            .sortable()
+            .resizable(true)
+            .width(300.),
+        Column::new("forwarder", "Forwarder")
+            .sortable()
+            .resizable(true)
+            .width(300.),
+    ]
+}
+
+impl ExportsView {
+    pub fn new(window: &mut Window, cx: &mut Context<RustDump>) -> Self {
+        Self {
+            directory: None,
+            directory_table: HeaderTable::new(window, cx),
+            exports_table: DataTable::new(export_columns(), window, cx),
+        }
+    }
+
+    pub fn load(
+        &mut self,
+        directory: Option<ExportDirectory>,
+        window: &mut Window,
+        cx: &mut Context<RustDump>,
+    ) {
+        let Some(dir) = &directory else {
+            self.directory_table.load(vec![], window, cx);
+            self.exports_table.load(vec![], window, cx);
+            self.directory = None;
+            return;
+        };
+
+        let off = dir.offset;
+        let data = vec![
+            HeaderData::new(off, "Characteristics", dir.characteristics, "Reserved"),
+            HeaderData::new(
+                off + 4,
+                "TimeDateStamp",
+                dir.time_date_stamp,
+                "Creation time",
+            ),
+            HeaderData::new(off + 8, "MajorVersion", dir.major_version, "Major version"),
+            HeaderData::new(off + 10, "MinorVersion", dir.minor_version, "Minor version"),
+            HeaderData::new(off + 12, "Name", dir.name.clone(), "DLL name"),
+            HeaderData::new(off + 16, "Base", dir.ordinal_base, "Ordinal base"),
+            HeaderData::new(
+                off + 20,
+                "NumberOfFunctions",
+                dir.number_of_functions,
+                "Entries in the address table",
+            ),
+            HeaderData::new(
+                off + 24,
+                "NumberOfNames",
+                dir.number_of_names,
+                "Entries in the name pointer table",
+            ),
+            HeaderData::new(
+                off + 28,
+                "AddressOfFunctions",
+                format!("0x{:08X}", dir.address_of_functions),
+                "Export address table RVA",
+            ),
+            HeaderData::new(
+                off + 32,
+                "AddressOfNames",
+                format!("0x{:08X}", dir.address_of_names),
+                "Name pointer table RVA",
+            ),
+            HeaderData::new(
+                off + 36,
+                "AddressOfNameOrdinals",
+                format!("0x{:08X}", dir.address_of_name_ordinals),
+                "Ordinal table RVA",
+            ),
+        ];
+        self.directory_table.load(data, window, cx);
+
+        let rows = dir
+            .exports
+            .iter()
+            .map(|export| {
+                vec![
+                    export.ordinal.to_string(),
+                    format!("0x{:08X}", export.rva),
+                    export.name.clone().unwrap_or_default(),
+                    export.forwarder.clone().unwrap_or_default(),
+                ]
+            })
+            .collect();
+        self.exports_table.load(rows, window, cx);
+
+        self.directory = directory;
+    }
+
+    pub fn scroll_table(&self) -> &DataTable {
+        &self.exports_table
+    }
+
+    pub fn render(&self) -> AnyElement {
+        if self.directory.is_none() {
+            return div().p_2().child("No export directory").into_any_element();
+        }
+
+        div()
+            .grid()
+            .grid_cols(2)
+            .grid_rows(1)
+            .size_full()
+            .child(self.directory_table.render())
+            .child(self.exports_table.render())
+            .into_any_element()
+    }
+}
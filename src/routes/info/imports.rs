@@ -0,0 +1,305 @@
+use gpui::{AnyElement, Context, IntoElement, ParentElement, Styled, Window, div};
+use gpui_component::{StyledExt, table::Column};
+
+use crate::{
+    InfoDisplayPage, RustDump,
+    components::datatable::DataTable,
+    pe::{delay_imports::DelayImportedDll, imports::ImportedDll},
+};
+
+pub struct ImportsView {
+    pub dlls: Vec<ImportedDll>,
+    pub delay_dlls: Vec<DelayImportedDll>,
+    dll_table: DataTable,
+    /// Built when the DLL's page is first opened
+    function_tables: Vec<Option<DataTable>>,
+    delay_table: DataTable,
+    delay_function_tables: Vec<Option<DataTable>>,
+}
+
+fn dll_columns() -> Vec<Column> {
+    vec![
+        Column::new("name", "DLL").resizable(true).width(200.),
+        Column::new("count", "Functions")
+            .resizable(true)
+            .width(100.),
+        Column::new("oft", "OriginalFirstThunk")
+            .resizable(true)
+            .width(150.),
+        Column::new("timestamp", "TimeDateStamp")
+            .resizable(true)
+            .width(125.),
+        Column::new("fwd", "ForwarderChain")
+            .resizable(true)
+            .width(125.),
+        Column::new("name_rva", "Name RVA")
+            .resizable(true)
+            .width(100.),
+        Column::new("ft", "FirstThunk").resizable(true).width(100.),
+    ]
+}
+
+fn function_columns() -> Vec<Column> {
+    vec![
+        Column::new("iat", "IAT RVA")
+            .sortable()
+            .resizable(true)
+            .width(100.),
+        Column::new("thunk", "Thunk").resizable(true).width(175.),
+        Column::new("hint", "Hint")
+            .sortable()
+            .resizable(true)
+            .width(75.),
+        Column::new("ordinal", "Ordinal")
+            .sortable()
+            .resizable(true)
+            .width(75.),
+        Column::new("name", "Name")
+            .sortable()
+            .resizable(true)
+            .width(300.),
+    ]
+}
+
+fn delay_columns() -> Vec<Column> {
+    vec![
+        Column::new("name", "Delay DLL").resizable(true).width(200.),
+        Column::new("count", "Functions")
+            .resizable(true)
+            .width(100.),
+        Column::new("attributes", "Attributes")
+            .resizable(true)
+            .width(125.),
+        Column::new("handle", "Module Handle")
+            .resizable(true)
+            .width(125.),
+        Column::new("iat", "IAT").resizable(true).width(100.),
+        Column::new("int", "Name Table").resizable(true).width(100.),
+        Column::new("bound", "Bound IAT")
+            .resizable(true)
+            .width(100.),
+        Column::new("unload", "Unload IAT")
+            .resizable(true)
+            .width(100.),
+        Column::new("timestamp", "TimeDateStamp")
+            .resizable(true)
+            .width(125.),
+    ]
+}
+
+fn delay_function_columns() -> Vec<Column> {
+    let mut columns = function_columns();
+    columns.extend([
+        Column::new("iat_value", "IAT Value")
+            .resizable(true)
+            .width(175.),
+        Column::new("bound", "Bound IAT")
+            .resizable(true)
+            .width(175.),
+        Column::new("unload", "Unload IAT")
+            .resizable(true)
+            .width(175.),
+    ]);
+    columns
+}
+
+fn optional_hex(value: Option<u64>) -> String {
+    value.map(|v| format!("0x{:X}", v)).unwrap_or_default()
+}
+
+impl ImportsView {
+    pub fn new(window: &mut Window, cx: &mut Context<RustDump>) -> Self {
+        Self {
+            dlls: vec![],
+            delay_dlls: vec![],
+            dll_table: DataTable::new(dll_columns(), window, cx),
+            function_tables: vec![],
+            delay_table: DataTable::new(delay_columns(), window, cx),
+            delay_function_tables: vec![],
+        }
+    }
+
+    pub fn load(
+        &mut self,
+        dlls: Vec<ImportedDll>,
+        delay_dlls: Vec<DelayImportedDll>,
+        window: &mut Window,
+        cx: &mut Context<RustDump>,
+    ) {
+        let rows = dlls
+            .iter()
+            .map(|dll| {
+                vec![
+                    dll.name.clone(),
+                    dll.functions.len().to_string(),
+                    format!("0x{:08X}", dll.original_first_thunk),
+                    format!("0x{:08X}", dll.time_date_stamp),
+                    format!("0x{:08X}", dll.forwarder_chain),
+                    format!("0x{:08X}", dll.name_rva),
+                    format!("0x{:08X}", dll.first_thunk),
+                ]
+            })
+            .collect();
+        self.dll_table.load(rows, window, cx);
+
+        self.function_tables = dlls.iter().map(|_| None).collect();
+
+        let rows = delay_dlls
+            .iter()
+            .map(|dll| {
+                let layout = if dll.rva_based { "RVA" } else { "VA" };
+                vec![
+                    dll.name.clone(),
+                    dll.functions.len().to_string(),
+                    format!("0x{:08X} ({})", dll.attributes, layout),
+                    format!("0x{:08X}", dll.module_handle_rva),
+                    format!("0x{:08X}", dll.iat_rva),
+                    format!("0x{:08X}", dll.int_rva),
+                    format!("0x{:08X}", dll.bound_iat_rva),
+                    format!("0x{:08X}", dll.unload_iat_rva),
+                    format!("0x{:08X}", dll.time_date_stamp),
+                ]
+            })
+            .collect();
+        self.delay_table.load(rows, window, cx);
+
+        self.delay_function_tables = delay_dlls.iter().map(|_| None).collect();
+
+        self.dlls = dlls;
+        self.delay_dlls = delay_dlls;
+    }
+
+    /// Builds the function table of a DLL page if it is not built yet
+    pub fn open(
+        &mut self,
+        page: &InfoDisplayPage,
+        window: &mut Window,
+        cx: &mut Context<RustDump>,
+    ) {
+        match page {
+            InfoDisplayPage::Import(idx) => {
+                let slot = self.function_tables.get_mut(*idx);
+                let (Some(dll), Some(slot)) = (self.dlls.get(*idx), slot) else {
+                    return;
+                };
+                if slot.is_some() {
+                    return;
+                }
+                let rows = dll
+                    .functions
+                    .iter()
+                    .map(|func| {
+                        vec![
+                            format!("0x{:08X}", func.iat_rva),
+                            format!("0x{:X}", func.thunk),
+                            func.hint.map(|h| h.to_string()).unwrap_or_default(),
+                            func.ordinal.map(|o| o.to_string()).unwrap_or_default(),
+                            func.name.clone().unwrap_or_default(),
+                        ]
+                    })
+                    .collect();
+                let mut table = DataTable::new(function_columns(), window, cx);
+                table.load(rows, window, cx);
+                *slot = Some(table);
+            }
+            InfoDisplayPage::DelayImport(idx) => {
+                let slot = self.delay_function_tables.get_mut(*idx);
+                let (Some(dll), Some(slot)) = (self.delay_dlls.get(*idx), slot) else {
+                    return;
+                };
+                if slot.is_some() {
+                    return;
+                }
+                let rows = dll
+                    .functions
+                    .iter()
+                    .map(|delay| {
+                        let func = &delay.function;
+                        vec![
+                            format!("0x{:08X}", func.iat_rva),
+                            format!("0x{:X}", func.thunk),
+                            func.hint.map(|h| h.to_string()).unwrap_or_default(),
+                            func.ordinal.map(|o| o.to_string()).unwrap_or_default(),
+                            func.name.clone().unwrap_or_default(),
+                            optional_hex(delay.iat_value),
+                            optional_hex(delay.bound_value),
+                            optional_hex(delay.unload_value),
+                        ]
+                    })
+                    .collect();
+                let mut table = DataTable::new(delay_function_columns(), window, cx);
+                table.load(rows, window, cx);
+                *slot = Some(table);
+            }
+            _ => {}
+        }
+    }
+
+    /// Main table of an import page, `None` for other pages
+    pub fn scroll_table(&self, page: &InfoDisplayPage) -> Option<&DataTable> {
+        match page {
+            InfoDisplayPage::Imports => Some(&self.dll_table),
+            InfoDisplayPage::Import(idx) => self.function_tables.get(*idx)?.as_ref(),
+            InfoDisplayPage::DelayImport(idx) => self.delay_function_tables.get(*idx)?.as_ref(),
+            _ => None,
+        }
+    }
+
+    pub fn render_overview(&self) -> AnyElement {
+        if self.delay_dlls.is_empty() {
+            return div()
+                .size_full()
+                .child(self.dll_table.render())
+                .into_any_element();
+        }
+
+        div()
+            .grid()
+            .grid_cols(1)
+            .grid_rows(2)
+            .size_full()
+            .child(self.dll_table.render())
+            .child(self.delay_table.render())
+            .into_any_element()
+    }
+
+    pub fn render_dll(&self, idx: usize) -> AnyElement {
+        let (Some(dll), Some(table)) = (
+            self.dlls.get(idx),
+            self.function_tables.get(idx).and_then(Option::as_ref),
+        ) else {
+            return div().into_any_element();
+        };
+
+        div()
+            .v_flex()
+            .size_full()
+            .child(
+                div()
+                    .p_2()
+                    .child(format!("{} ({} functions)", dll.name, dll.functions.len())),
+            )
+            .child(div().size_full().child(table.render()))
+            .into_any_element()
+    }
+
+    pub fn render_delay_dll(&self, idx: usize) -> AnyElement {
+        let (Some(dll), Some(table)) = (
+            self.delay_dlls.get(idx),
+            self.delay_function_tables.get(idx).and_then(Option::as_ref),
+        ) else {
+            return div().into_any_element();
+        };
+
+        div()
+            .v_flex()
+            .size_full()
+            .child(div().p_2().child(format!(
+                "{} (delay loaded, {} functions)",
+                dll.name,
+                dll.functions.len()
+            )))
+            .child(div().size_full().child(table.render()))
+            .into_any_element()
+    }
+}
@@ -1,25 +1,11 @@
-use std::{collections::HashMap, fs, path::Path};
-const DIR_NAMES: [&str; 16] = [
-    "Export Directory",
-    "Import Directory",
-    "Resource Directory",
-    "Exception Directory",
-    "Security Directory",
-    "Base Relocation Directory",
-    "Debug Directory",
-    "Architecture Directory",
-    "Global Pointer Directory",
-    "TLS Directory",
-    "Load Configuration Directory",
-    "Bound Import Directory",
-    "Import Address Table Directory",
-    "Delay Import Directory",
-    "COM Descriptor Directory",
-    "Reserved Directory",
-];
+use std::{
+    collections::{HashMap, HashSet},
+    mem::{self, Discriminant},
+    sync::Arc,
+};
 
 use gpui::{
-    AnyElement, Context, IntoElement, ParentElement, SharedString, Styled, Window, div,
+    AnyElement, App, Context, IntoElement, ParentElement, SharedString, Styled, Window, div,
     transparent_black,
 };
 use gpui_component::{
@@ -38,10 +24,19 @@ use crate::{
     InfoDisplayPage, Route, RustDump,
     components::{
         asciiview::AsciiView,
+        datatable::ScrollTable,
         headertable::{HeaderData, HeaderTable},
         hexview::Hexview,
     },
-    routes::info::section_hdrs_table,
+    loader::LoadedFile,
+    pe::{self, address::AddressSpace, directories::Directories, flags, resources},
+    routes::info::{
+        bound_imports::BoundImportsView, clr::ClrView, debug::DebugView,
+        exceptions::ExceptionsView, exports::ExportsView, imports::ImportsView,
+        load_config::LoadConfigView, relocations::RelocationsView, resources::ResourcesView,
+        rich::RichView, section::SectionView, section_hdrs_table, security::SecurityView,
+        tls::TlsView,
+    },
 };
 
 pub struct Info {
@@ -55,6 +50,25 @@ pub struct Info {
     data_dir_table: HeaderTable,
     sections: HashMap<SharedString, SectionHeader>,
     section_headers_table: SectionsTable,
+    section_views: HashMap<SharedString, SectionView>,
+    imports: ImportsView,
+    bound_imports: BoundImportsView,
+    exports: ExportsView,
+    resources: ResourcesView,
+    relocations: RelocationsView,
+    tls: TlsView,
+    debug: DebugView,
+    load_config: LoadConfigView,
+    exceptions: ExceptionsView,
+    security: SecurityView,
+    clr: ClrView,
+    rich: RichView,
+    /// File contents, kept to build section views on demand
+    bytes: Arc<Vec<u8>>,
+    /// Directories parsed by the loader, moved into their view when its page is first opened
+    pending: Directories,
+    /// Pages whose view has already been built from `pending`
+    built: HashSet<Discriminant<InfoDisplayPage>>,
 }
 
 impl Info {
@@ -77,9 +91,33 @@ impl Info {
             data_dir_table: HeaderTable::new(window, cx),
             sections: HashMap::new(),
             section_headers_table: SectionsTable::new(window, cx),
+            section_views: HashMap::new(),
+            imports: ImportsView::new(window, cx),
+            bound_imports: BoundImportsView::new(window, cx),
+            exports: ExportsView::new(window, cx),
+            resources: ResourcesView::new(),
+            relocations: RelocationsView::new(window, cx),
+            tls: TlsView::new(window, cx),
+            debug: DebugView::new(window, cx),
+            load_config: LoadConfigView::new(window, cx),
+            exceptions: ExceptionsView::new(window, cx),
+            security: SecurityView::new(window, cx),
+            clr: ClrView::new(window, cx),
+            rich: RichView::new(window, cx),
+            bytes: Arc::new(vec![]),
+            pending: Directories::default(),
+            built: HashSet::new(),
         }
     }
+    /// Section names in the order they are mapped in memory
+    fn sorted_sections(&self) -> Vec<SharedString> {
+        let mut sections: Vec<(&SharedString, &SectionHeader)> = self.sections.iter().collect();
+        sections.sort_by_key(|(_, sct)| sct.virtual_address);
+        sections.into_iter().map(|(name, _)| name.clone()).collect()
+    }
+
     pub fn render_route(&self, cx: &mut Context<RustDump>, app: &RustDump) -> AnyElement {
+        let tab = app.tab();
         let sidebar = div()
             .v_flex()
             .text_left()
@@ -88,8 +126,12 @@ impl Info {
                     .h_flex()
                     .child(
                         Button::new("dos_header")
-                            .on_click(cx.listener(|app, _event, _window, _cx| {
-                                app.info_page = InfoDisplayPage::DOSHeaders;
+                            .on_click(cx.listener(|app, _event, window, cx| {
+                                app.tab_mut().set_info_page(
+                                    InfoDisplayPage::DOSHeaders,
+                                    window,
+                                    cx,
+                                );
                             }))
                             .child(
                                 Icon::new(Icon::empty())
@@ -106,8 +148,9 @@ impl Info {
                     .h_flex()
                     .child(
                         Button::new("dos_stub")
-                            .on_click(cx.listener(|app, _event, _window, _cx| {
-                                app.info_page = InfoDisplayPage::DOSStub;
+                            .on_click(cx.listener(|app, _event, window, cx| {
+                                app.tab_mut()
+                                    .set_info_page(InfoDisplayPage::DOSStub, window, cx);
                             }))
                             .child(
                                 Icon::new(Icon::empty())
@@ -119,6 +162,25 @@ impl Info {
                     )
                     .gap_2(),
             )
+            .child(
+                div().ml_2().child(
+                    Button::new("rich_header")
+                        .flex()
+                        .justify_start()
+                        .text_left()
+                        .on_click(cx.listener(|app, _event, window, cx| {
+                            app.tab_mut()
+                                .set_info_page(InfoDisplayPage::RichHeader, window, cx);
+                        }))
+                        .child(
+                            Icon::new(Icon::empty())
+                                .path("icons/file-spreadsheet.svg")
+                                .text_color(cx.theme().foreground),
+                        )
+                        .child("Rich Hdr")
+                        .custom(self.custom_btn),
+                ),
+            )
             .child(
                 div()
                     .h_flex()
@@ -126,7 +188,8 @@ impl Info {
                         Button::new("nt_headers")
                             .dropdown_caret(true)
                             .on_click(cx.listener(|app, _event, _window, _cx| {
-                                app.expand_nt = !app.expand_nt;
+                                let tab = app.tab_mut();
+                                tab.expand_nt = !tab.expand_nt;
                             }))
                             .child(
                                 Icon::new(Icon::empty())
@@ -144,14 +207,18 @@ impl Info {
                     .flex()
                     .text_left()
                     .justify_start()
-                    .child(if app.expand_nt {
+                    .child(if tab.expand_nt {
                         div().children(vec![
                             Button::new("file_header")
                                 .flex()
                                 .justify_start()
                                 .text_left()
-                                .on_click(cx.listener(|app, _event, _window, _cx| {
-                                    app.info_page = InfoDisplayPage::FileHdr;
+                                .on_click(cx.listener(|app, _event, window, cx| {
+                                    app.tab_mut().set_info_page(
+                                        InfoDisplayPage::FileHdr,
+                                        window,
+                                        cx,
+                                    );
                                 }))
                                 .child(
                                     Icon::new(Icon::empty())
@@ -164,8 +231,12 @@ impl Info {
                                 .flex()
                                 .justify_start()
                                 .text_left()
-                                .on_click(cx.listener(|app, _event, _window, _cx| {
-                                    app.info_page = InfoDisplayPage::OptHdr;
+                                .on_click(cx.listener(|app, _event, window, cx| {
+                                    app.tab_mut().set_info_page(
+                                        InfoDisplayPage::OptHdr,
+                                        window,
+                                        cx,
+                                    );
                                 }))
                                 .child(
                                     Icon::new(Icon::empty())
@@ -179,14 +250,120 @@ impl Info {
                         div()
                     }),
             )
+            .child(
+                div()
+                    .h_flex()
+                    .child(
+                        Button::new("resources")
+                            .dropdown_caret(true)
+                            .on_click(cx.listener(|app, _event, _window, _cx| {
+                                let tab = app.tab_mut();
+                                tab.expand_resources = !tab.expand_resources;
+                            }))
+                            .child(
+                                Icon::new(Icon::empty())
+                                    .path("icons/list-tree.svg")
+                                    .text_color(cx.theme().foreground),
+                            )
+                            .child("Resources")
+                            .custom(self.custom_btn),
+                    )
+                    .gap_2(),
+            )
+            .child(if tab.expand_resources {
+                div()
+                    .ml_2()
+                    .children(
+                        self.resources
+                            .types
+                            .iter()
+                            .enumerate()
+                            .map(|(type_idx, ty)| {
+                                let first_leaf = self.resources.first_leaf(type_idx);
+                                div()
+                                    .child(
+                                        Button::new(("resource_type", type_idx))
+                                            .dropdown_caret(true)
+                                            .flex()
+                                            .justify_start()
+                                            .text_left()
+                                            .on_click(cx.listener(
+                                                move |app, _event, _window, _cx| {
+                                                    let tab = app.tab_mut();
+                                                    if !tab
+                                                        .expanded_resource_types
+                                                        .remove(&type_idx)
+                                                    {
+                                                        tab.expanded_resource_types
+                                                            .insert(type_idx);
+                                                    }
+                                                },
+                                            ))
+                                            .child(
+                                                Icon::new(Icon::empty())
+                                                    .path("icons/list-tree.svg")
+                                                    .text_color(cx.theme().foreground),
+                                            )
+                                            .child(resources::type_name(&ty.id))
+                                            .custom(self.custom_btn),
+                                    )
+                                    .child(if tab.expanded_resource_types.contains(&type_idx) {
+                                        div().ml_2().children(ty.leaves.iter().enumerate().map(
+                                            |(i, leaf)| {
+                                                let leaf_idx = first_leaf + i;
+                                                Button::new(("resource", leaf_idx))
+                                                    .flex()
+                                                    .justify_start()
+                                                    .text_left()
+                                                    .on_click(cx.listener(
+                                                        move |app, _event, window, cx| {
+                                                            app.tab_mut().set_info_page(
+                                                                InfoDisplayPage::Resource(leaf_idx),
+                                                                window,
+                                                                cx,
+                                                            );
+                                                        },
+                                                    ))
+                                                    .child(
+                                                        Icon::new(Icon::empty())
+                                                            .path("icons/file-spreadsheet.svg")
+                                                            .text_color(cx.theme().foreground),
+                                                    )
+                                                    .child(format!(
+                                                        "{} / 0x{:04X}",
+                                                        leaf.name, leaf.language
+                                                    ))
+                                                    .custom(self.custom_btn)
+                                            },
+                                        ))
+                                    } else {
+                                        div()
+                                    })
+                            }),
+                    )
+                    .child(if self.resources.truncated {
+                        div()
+                            .p_1()
+                            .text_color(cx.theme().warning)
+                            .child("Too many resources, list truncated")
+                    } else {
+                        div()
+                    })
+            } else {
+                div()
+            })
             .child(
                 div()
                     .h_flex()
                     .child(
                         Button::new("section_hdrs")
                             .size_full()
-                            .on_click(cx.listener(|app, _event, _window, _cx| {
-                                app.info_page = InfoDisplayPage::SectionHeaders;
+                            .on_click(cx.listener(|app, _event, window, cx| {
+                                app.tab_mut().set_info_page(
+                                    InfoDisplayPage::SectionHeaders,
+                                    window,
+                                    cx,
+                                );
                             }))
                             .child(
                                 Icon::new(Icon::empty())
@@ -198,14 +375,310 @@ impl Info {
                     )
                     .gap_2(),
             )
-            /*.child(
+            .child(
+                div()
+                    .h_flex()
+                    .child(
+                        Button::new("imports")
+                            .dropdown_caret(true)
+                            .on_click(cx.listener(|app, _event, window, cx| {
+                                let tab = app.tab_mut();
+                                tab.set_info_page(InfoDisplayPage::Imports, window, cx);
+                                tab.expand_imports = !tab.expand_imports;
+                            }))
+                            .child(
+                                Icon::new(Icon::empty())
+                                    .path("icons/list-tree.svg")
+                                    .text_color(cx.theme().foreground),
+                            )
+                            .child("Imports")
+                            .custom(self.custom_btn),
+                    )
+                    .gap_2(),
+            )
+            .child(if tab.expand_imports {
+                div()
+                    .ml_2()
+                    .children(self.imports.dlls.iter().enumerate().map(|(i, dll)| {
+                        Button::new(("import", i))
+                            .flex()
+                            .justify_start()
+                            .text_left()
+                            .on_click(cx.listener(move |app, _event, window, cx| {
+                                app.tab_mut()
+                                    .set_info_page(InfoDisplayPage::Import(i), window, cx);
+                            }))
+                            .child(
+                                Icon::new(Icon::empty())
+                                    .path("icons/file-spreadsheet.svg")
+                                    .text_color(cx.theme().foreground),
+                            )
+                            .child(dll.name.clone())
+                            .custom(self.custom_btn)
+                    }))
+                    .children(self.imports.delay_dlls.iter().enumerate().map(|(i, dll)| {
+                        Button::new(("delay_import", i))
+                            .flex()
+                            .justify_start()
+                            .text_left()
+                            .on_click(cx.listener(move |app, _event, window, cx| {
+                                app.tab_mut().set_info_page(
+                                    InfoDisplayPage::DelayImport(i),
+                                    window,
+                                    cx,
+                                );
+                            }))
+                            .child(
+                                Icon::new(Icon::empty())
+                                    .path("icons/file-spreadsheet.svg")
+                                    .text_color(cx.theme().foreground),
+                            )
+                            .child(format!("{} (delay)", dll.name))
+                            .custom(self.custom_btn)
+                    }))
+            } else {
+                div()
+            })
+            .child(
+                div()
+                    .h_flex()
+                    .child(
+                        Button::new("bound_imports")
+                            .on_click(cx.listener(|app, _event, window, cx| {
+                                app.tab_mut().set_info_page(
+                                    InfoDisplayPage::BoundImports,
+                                    window,
+                                    cx,
+                                );
+                            }))
+                            .child(
+                                Icon::new(Icon::empty())
+                                    .path("icons/file-spreadsheet.svg")
+                                    .text_color(cx.theme().foreground),
+                            )
+                            .child("Bound Imports")
+                            .custom(self.custom_btn),
+                    )
+                    .gap_2(),
+            )
+            .child(
+                div()
+                    .h_flex()
+                    .child(
+                        Button::new("exports")
+                            .on_click(cx.listener(|app, _event, window, cx| {
+                                app.tab_mut()
+                                    .set_info_page(InfoDisplayPage::Exports, window, cx);
+                            }))
+                            .child(
+                                Icon::new(Icon::empty())
+                                    .path("icons/file-spreadsheet.svg")
+                                    .text_color(cx.theme().foreground),
+                            )
+                            .child("Exports")
+                            .custom(self.custom_btn),
+                    )
+                    .gap_2(),
+            )
+            .child(
+                div()
+                    .h_flex()
+                    .child(
+                        Button::new("relocations")
+                            .on_click(cx.listener(|app, _event, window, cx| {
+                                app.tab_mut().set_info_page(
+                                    InfoDisplayPage::Relocations,
+                                    window,
+                                    cx,
+                                );
+                            }))
+                            .child(
+                                Icon::new(Icon::empty())
+                                    .path("icons/file-spreadsheet.svg")
+                                    .text_color(cx.theme().foreground),
+                            )
+                            .child("Relocations")
+                            .custom(self.custom_btn),
+                    )
+                    .gap_2(),
+            )
+            .child(
+                div()
+                    .h_flex()
+                    .child(
+                        Button::new("tls")
+                            .on_click(cx.listener(|app, _event, window, cx| {
+                                app.tab_mut()
+                                    .set_info_page(InfoDisplayPage::Tls, window, cx);
+                            }))
+                            .child(
+                                Icon::new(Icon::empty())
+                                    .path("icons/file-spreadsheet.svg")
+                                    .text_color(cx.theme().foreground),
+                            )
+                            .child("TLS")
+                            .custom(self.custom_btn),
+                    )
+                    .gap_2(),
+            )
+            .child(
+                div()
+                    .h_flex()
+                    .child(
+                        Button::new("debug")
+                            .on_click(cx.listener(|app, _event, window, cx| {
+                                app.tab_mut()
+                                    .set_info_page(InfoDisplayPage::Debug, window, cx);
+                            }))
+                            .child(
+                                Icon::new(Icon::empty())
+                                    .path("icons/file-spreadsheet.svg")
+                                    .text_color(cx.theme().foreground),
+                            )
+                            .child("Debug")
+                            .custom(self.custom_btn),
+                    )
+                    .gap_2(),
+            )
+            .child(
+                div()
+                    .h_flex()
+                    .child(
+                        Button::new("load_config")
+                            .on_click(cx.listener(|app, _event, window, cx| {
+                                app.tab_mut().set_info_page(
+                                    InfoDisplayPage::LoadConfig,
+                                    window,
+                                    cx,
+                                );
+                            }))
+                            .child(
+                                Icon::new(Icon::empty())
+                                    .path("icons/file-spreadsheet.svg")
+                                    .text_color(cx.theme().foreground),
+                            )
+                            .child("Load Config")
+                            .custom(self.custom_btn),
+                    )
+                    .gap_2(),
+            )
+            .child(
+                div()
+                    .h_flex()
+                    .child(
+                        Button::new("exceptions")
+                            .on_click(cx.listener(|app, _event, window, cx| {
+                                app.tab_mut().set_info_page(
+                                    InfoDisplayPage::Exceptions,
+                                    window,
+                                    cx,
+                                );
+                            }))
+                            .child(
+                                Icon::new(Icon::empty())
+                                    .path("icons/file-spreadsheet.svg")
+                                    .text_color(cx.theme().foreground),
+                            )
+                            .child("Exceptions")
+                            .custom(self.custom_btn),
+                    )
+                    .gap_2(),
+            )
+            .child(
+                div()
+                    .h_flex()
+                    .child(
+                        Button::new("security")
+                            .on_click(cx.listener(|app, _event, window, cx| {
+                                app.tab_mut()
+                                    .set_info_page(InfoDisplayPage::Security, window, cx);
+                            }))
+                            .child(
+                                Icon::new(Icon::empty())
+                                    .path("icons/file-spreadsheet.svg")
+                                    .text_color(cx.theme().foreground),
+                            )
+                            .child("Security")
+                            .custom(self.custom_btn),
+                    )
+                    .gap_2(),
+            )
+            .child(
+                div()
+                    .h_flex()
+                    .child(
+                        Button::new("clr")
+                            .dropdown_caret(true)
+                            .on_click(cx.listener(|app, _event, window, cx| {
+                                let tab = app.tab_mut();
+                                tab.set_info_page(InfoDisplayPage::Clr, window, cx);
+                                tab.expand_clr = !tab.expand_clr;
+                            }))
+                            .child(
+                                Icon::new(Icon::empty())
+                                    .path("icons/list-tree.svg")
+                                    .text_color(cx.theme().foreground),
+                            )
+                            .child(".NET")
+                            .custom(self.custom_btn),
+                    )
+                    .gap_2(),
+            )
+            .child(if tab.expand_clr && self.clr.clr.is_some() {
+                div()
+                    .ml_2()
+                    .children(self.clr.table_names.iter().enumerate().map(|(i, name)| {
+                        Button::new(("clr_table", i))
+                            .flex()
+                            .justify_start()
+                            .text_left()
+                            .on_click(cx.listener(move |app, _event, window, cx| {
+                                app.tab_mut().set_info_page(
+                                    InfoDisplayPage::ClrTable(i),
+                                    window,
+                                    cx,
+                                );
+                            }))
+                            .child(
+                                Icon::new(Icon::empty())
+                                    .path("icons/file-spreadsheet.svg")
+                                    .text_color(cx.theme().foreground),
+                            )
+                            .child(name.to_string())
+                            .custom(self.custom_btn)
+                    }))
+                    .child(
+                        Button::new("clr_user_strings")
+                            .flex()
+                            .justify_start()
+                            .text_left()
+                            .on_click(cx.listener(|app, _event, window, cx| {
+                                app.tab_mut().set_info_page(
+                                    InfoDisplayPage::ClrUserStrings,
+                                    window,
+                                    cx,
+                                );
+                            }))
+                            .child(
+                                Icon::new(Icon::empty())
+                                    .path("icons/file-spreadsheet.svg")
+                                    .text_color(cx.theme().foreground),
+                            )
+                            .child("User Strings")
+                            .custom(self.custom_btn),
+                    )
+            } else {
+                div()
+            })
+            .child(
                 div()
                     .h_flex()
                     .child(
                         Button::new("sections")
                             .dropdown_caret(true)
                             .on_click(cx.listener(|app, _event, _window, _cx| {
-                                app.expand_section = !app.expand_section;
+                                let tab = app.tab_mut();
+                                tab.expand_section = !tab.expand_section;
                             }))
                             .child(
                                 Icon::new(Icon::empty())
@@ -216,18 +689,22 @@ impl Info {
                             .custom(self.custom_btn),
                     )
                     .gap_2(),
-            )*/
-            .child(if app.expand_section {
+            )
+            .child(if tab.expand_section {
                 div()
                     .ml_2()
-                    .children(self.sections.iter().map(|(sct_name, sct)| {
+                    .children(self.sorted_sections().into_iter().map(|sct_name| {
                         let sct_name_cl = sct_name.clone();
                         Button::new(sct_name.clone())
                             .flex()
                             .justify_start()
                             .text_left()
-                            .on_click(cx.listener(move |app, _event, _window, _cx| {
-                                app.info_page = InfoDisplayPage::Section(sct_name_cl.clone());
+                            .on_click(cx.listener(move |app, _event, window, cx| {
+                                app.tab_mut().set_info_page(
+                                    InfoDisplayPage::Section(sct_name_cl.clone()),
+                                    window,
+                                    cx,
+                                );
                             }))
                             .child(
                                 Icon::new(Icon::empty())
@@ -258,7 +735,7 @@ impl Info {
                     .child(sidebar),
             )
             .child(
-                div().size_full().child(match &app.info_page {
+                div().size_full().child(match &tab.info_page {
                     InfoDisplayPage::DOSHeaders => div().child(self.dos_table.render()).size_full(),
                     InfoDisplayPage::DOSStub => div()
                         .grid()
@@ -281,69 +758,209 @@ impl Info {
                         .child(self.opt_header_table.render())
                         .child(self.data_dir_table.render())
                         .size_full(),
-                    InfoDisplayPage::Section(sct) => {
-                        div().child(SharedString::new(sct.clone().as_str()))
-                    }
+                    InfoDisplayPage::Section(sct) => match self.section_views.get(sct) {
+                        Some(view) => div().size_full().child(view.render(cx)),
+                        None => div().child(sct.clone()),
+                    },
                     InfoDisplayPage::SectionHeaders => {
                         div().size_full().child(self.section_headers_table.render())
                     }
+                    InfoDisplayPage::Imports => {
+                        div().size_full().child(self.imports.render_overview())
+                    }
+                    InfoDisplayPage::Import(idx) => {
+                        div().size_full().child(self.imports.render_dll(*idx))
+                    }
+                    InfoDisplayPage::DelayImport(idx) => {
+                        div().size_full().child(self.imports.render_delay_dll(*idx))
+                    }
+                    InfoDisplayPage::BoundImports => {
+                        div().size_full().child(self.bound_imports.render())
+                    }
+                    InfoDisplayPage::Exports => div().size_full().child(self.exports.render()),
+                    InfoDisplayPage::Resource(idx) => div()
+                        .size_full()
+                        .child(self.resources.render_leaf(*idx, cx)),
+                    InfoDisplayPage::Relocations => {
+                        div().size_full().child(self.relocations.render())
+                    }
+                    InfoDisplayPage::Tls => div().size_full().child(self.tls.render()),
+                    InfoDisplayPage::Debug => div().size_full().child(self.debug.render()),
+                    InfoDisplayPage::LoadConfig => {
+                        div().size_full().child(self.load_config.render())
+                    }
+                    InfoDisplayPage::Exceptions => {
+                        div().size_full().child(self.exceptions.render())
+                    }
+                    InfoDisplayPage::RichHeader => div().size_full().child(self.rich.render()),
+                    InfoDisplayPage::Security => div().size_full().child(self.security.render()),
+                    InfoDisplayPage::Clr => div().size_full().child(self.clr.render_overview()),
+                    InfoDisplayPage::ClrTable(idx) => {
+                        div().size_full().child(self.clr.render_table(*idx))
+                    }
+                    InfoDisplayPage::ClrUserStrings => {
+                        div().size_full().child(self.clr.render_user_strings())
+                    }
                 }),
             )
             .into_any_element()
     }
 
-    pub fn load_file(&mut self, path: &Path, cx: &mut Context<RustDump>, window: &mut Window) {
-        let Ok(bytes) = fs::read(path) else {
-            panic!("Failed to read file");
+    /// The main table of `page`, `None` for pages without one
+    fn scroll_table(&self, page: &InfoDisplayPage) -> Option<&dyn ScrollTable> {
+        match page {
+            InfoDisplayPage::DOSHeaders => Some(&self.dos_table),
+            InfoDisplayPage::DOSStub | InfoDisplayPage::Resource(_) => None,
+            InfoDisplayPage::RichHeader => Some(self.rich.scroll_table()),
+            InfoDisplayPage::FileHdr => Some(&self.file_header_table),
+            InfoDisplayPage::OptHdr => Some(&self.opt_header_table),
+            InfoDisplayPage::SectionHeaders => Some(&self.section_headers_table),
+            InfoDisplayPage::Section(sct) => self
+                .section_views
+                .get(sct)
+                .map(|view| view.scroll_table() as &dyn ScrollTable),
+            InfoDisplayPage::Imports
+            | InfoDisplayPage::Import(_)
+            | InfoDisplayPage::DelayImport(_) => self
+                .imports
+                .scroll_table(page)
+                .map(|table| table as &dyn ScrollTable),
+            InfoDisplayPage::BoundImports => Some(self.bound_imports.scroll_table()),
+            InfoDisplayPage::Exports => Some(self.exports.scroll_table()),
+            InfoDisplayPage::Relocations => Some(self.relocations.scroll_table()),
+            InfoDisplayPage::Tls => Some(self.tls.scroll_table()),
+            InfoDisplayPage::Debug => Some(self.debug.scroll_table()),
+            InfoDisplayPage::LoadConfig => Some(self.load_config.scroll_table()),
+            InfoDisplayPage::Exceptions => Some(self.exceptions.scroll_table()),
+            InfoDisplayPage::Security => Some(self.security.scroll_table()),
+            InfoDisplayPage::Clr
+            | InfoDisplayPage::ClrTable(_)
+            | InfoDisplayPage::ClrUserStrings => self.clr.scroll_table(page),
+        }
+    }
+
+    fn build_section_view(
+        &mut self,
+        name: &SharedString,
+        window: &mut Window,
+        cx: &mut Context<RustDump>,
+    ) {
+        if self.section_views.contains_key(name) {
+            return;
+        }
+        let Some(pe_header) = &self.pe_header else {
+            return;
+        };
+        // Later sections win on duplicate names, like in `sections`
+        let Some(i) = pe_header
+            .section_headers
+            .iter()
+            .rposition(|hdr| String::from_utf8_lossy(&hdr.name).as_ref() == name.as_ref())
+        else {
+            return;
+        };
+        let space = AddressSpace::new(pe_header).with_file_size(self.bytes.len());
+        let entropy = self
+            .pending
+            .section_entropy
+            .get(i)
+            .copied()
+            .unwrap_or_default();
+        let view = SectionView::new(
+            &self.bytes,
+            &pe_header.section_headers[i],
+            space.section_table_offset() + i * 40,
+            &space,
+            entropy,
+            window,
+            cx,
+        );
+        self.section_views.insert(name.clone(), view);
+    }
+
+    /// Builds the view of a single table page the first time it is opened
+    fn build_view(
+        &mut self,
+        page: &InfoDisplayPage,
+        window: &mut Window,
+        cx: &mut Context<RustDump>,
+    ) {
+        if !self.built.insert(mem::discriminant(page)) {
+            return;
+        }
+        let Some(pe_header) = &self.pe_header else {
+            return;
         };
+        let space = AddressSpace::new(pe_header).with_file_size(self.bytes.len());
+        let dirs = &mut self.pending;
+        match page {
+            InfoDisplayPage::RichHeader => self.rich.load(dirs.rich.take(), window, cx),
+            InfoDisplayPage::BoundImports => self.bound_imports.load(
+                mem::take(&mut dirs.bound_imports),
+                mem::take(&mut dirs.missing_bindings),
+                window,
+                cx,
+            ),
+            InfoDisplayPage::Exports => self.exports.load(dirs.exports.take(), window, cx),
+            InfoDisplayPage::Relocations => self.relocations.load(
+                mem::take(&mut dirs.relocations),
+                pe_header.nt_header.image_file_header.machine,
+                &space,
+                window,
+                cx,
+            ),
+            InfoDisplayPage::Tls => self.tls.load(dirs.tls.take(), window, cx),
+            InfoDisplayPage::Debug => self.debug.load(mem::take(&mut dirs.debug), window, cx),
+            InfoDisplayPage::LoadConfig => {
+                self.load_config.load(dirs.load_config.take(), window, cx)
+            }
+            InfoDisplayPage::Exceptions => {
+                self.exceptions
+                    .load(mem::take(&mut dirs.exceptions), &space, window, cx)
+            }
+            InfoDisplayPage::Security => {
+                self.security
+                    .load(mem::take(&mut dirs.certificates), window, cx)
+            }
+            _ => {}
+        }
+    }
 
-        let pe_header = pe_parse::parse_pe_header(&bytes[..]).unwrap();
+    pub fn load_file(
+        &mut self,
+        file: &mut LoadedFile,
+        cx: &mut Context<RustDump>,
+        window: &mut Window,
+    ) -> anyhow::Result<()> {
+        let pe_header = file.pe_header()?.clone();
+        let mut dirs = file.directories.take().unwrap_or_default();
+        let space = AddressSpace::new(&pe_header).with_file_size(file.bytes.len());
 
         // Load DOS header
-        let dos_header = serde_json::to_value(&pe_header.dos_header);
-        let Ok(dos_header) = dos_header else { return };
+        let dos_header = serde_json::to_value(&pe_header.dos_header)?;
         let data = parse_data(
             dos_header,
-            |idx| {
-                let mut offset_string = String::new();
-                let offset_bytes = ImageDosHeader::get_offset(idx).unwrap_or(0).to_le_bytes();
-                offset_bytes
-                    .iter()
-                    .for_each(|b| push_hex(&mut offset_string, *b));
-                offset_string
-            },
+            |idx| header_offset_string(ImageDosHeader::get_offset(idx), 0),
             pe_parse::DOS_HEADER_MEANINGS.to_vec(),
         );
 
         self.dos_table.load(data, window, cx);
 
         // Load file header
-        let file_header = serde_json::to_value(&pe_header.nt_header.image_file_header);
-        let Ok(file_header) = file_header else { return };
+        let file_header = serde_json::to_value(&pe_header.nt_header.image_file_header)?;
         let data = parse_data(
             file_header,
-            |idx| {
-                let mut offset_string = String::new();
-                let offset_bytes = ImageFileHeader::get_offset(idx)
-                    .and_then(|offst| Some(offst + pe_header.dos_header.e_lfanew as u16))
-                    .unwrap_or(0)
-                    .to_le_bytes();
-                offset_bytes
-                    .iter()
-                    .for_each(|b| push_hex(&mut offset_string, *b));
-                offset_string
-            },
+            |idx| header_offset_string(ImageFileHeader::get_offset(idx), space.nt_headers_offset()),
             pe_parse::FILE_HEADER_MEANINGS.to_vec(),
         );
+        let data = decode_fields(data);
 
         self.file_header_table.load(data, window, cx);
 
-        let mut image_base = 0;
         // Load OPT headers
-        match pe_header.nt_header.optional_headers {
+        match &pe_header.nt_header.optional_headers {
             OptionalHeaders::OptionalHeaders32(opt32) => {
-                let opt_header = serde_json::to_value(&opt32);
-                let Ok(opt_header) = opt_header else { return };
+                let opt_header = serde_json::to_value(opt32)?;
 
                 let dir_data =
                     parse_data_directory_from_array(&opt32.data_directory, |idx| idx.to_string());
@@ -352,26 +969,18 @@ impl Info {
                 let data = parse_data(
                     opt_header,
                     |idx| {
-                        let mut offset_string = String::new();
-                        let offset_bytes = OptionalHeaders32::get_offset(idx)
-                            .and_then(|offst| {
-                                Some(offst + pe_header.dos_header.e_lfanew as u16 + 24)
-                            })
-                            .unwrap_or(0)
-                            .to_le_bytes();
-                        offset_bytes
-                            .iter()
-                            .for_each(|b| push_hex(&mut offset_string, *b));
-                        offset_string
+                        header_offset_string(
+                            OptionalHeaders32::get_offset(idx),
+                            space.optional_header_offset(),
+                        )
                     },
                     pe_parse::OPTIONAL_HEADER32_MEANINGS.to_vec(),
                 );
-                image_base = opt32.image_base as u64;
+                let data = decode_fields(data);
                 self.opt_header_table.load(data, window, cx);
             }
             OptionalHeaders::OptionalHeaders64(opt64) => {
-                let opt_header = serde_json::to_value(&opt64);
-                let Ok(opt_header) = opt_header else { return };
+                let opt_header = serde_json::to_value(opt64)?;
 
                 let dir_data =
                     parse_data_directory_from_array(&opt64.data_directory, |idx| idx.to_string());
@@ -380,21 +989,14 @@ impl Info {
                 let data = parse_data(
                     opt_header,
                     |idx| {
-                        let mut offset_string = String::new();
-                        let offset_bytes = OptionalHeaders64::get_offset(idx)
-                            .and_then(|offst| {
-                                Some(offst + pe_header.dos_header.e_lfanew as u16 + 24)
-                            })
-                            .unwrap_or(0)
-                            .to_le_bytes();
-                        offset_bytes
-                            .iter()
-                            .for_each(|b| push_hex(&mut offset_string, *b));
-                        offset_string
+                        header_offset_string(
+                            OptionalHeaders64::get_offset(idx),
+                            space.optional_header_offset(),
+                        )
                     },
                     pe_parse::OPTIONAL_HEADER64_MEANINGS.to_vec(),
                 );
-                image_base = opt64.image_base;
+                let data = decode_fields(data);
                 self.opt_header_table.load(data, window, cx);
             }
         }
@@ -405,11 +1007,25 @@ impl Info {
             sections.insert(SharedString::new(name.as_str()), hdr.clone());
         }
         let values: Vec<SectionHeader> = sections.clone().into_values().collect();
-        self.section_headers_table
-            .load(&values, window, cx, image_base);
+        self.section_headers_table.load(&values, window, cx, &space);
         self.sections = sections;
 
+        // The sidebar lists the DLLs, resources and metadata tables, every other
+        // view is built from `pending` when its page is first opened
+        self.imports.load(
+            mem::take(&mut dirs.imports),
+            mem::take(&mut dirs.delay_imports),
+            window,
+            cx,
+        );
+        self.resources
+            .load(mem::take(&mut dirs.resources), file.bytes.clone());
+        self.clr.load(dirs.clr.take(), window, cx);
+        self.pending = dirs;
+        self.bytes = file.bytes.clone();
+
         self.pe_header = Some(pe_header);
+        Ok(())
     }
 }
 
@@ -418,17 +1034,71 @@ impl Route for Info {
         self.render_route(cx, app)
     }
 
-    fn load(&mut self, cx: &mut Context<RustDump>, window: &mut Window, path: &Path) {
-        self.load_file(path, cx, window);
+    fn open_info_page(
+        &mut self,
+        page: &InfoDisplayPage,
+        window: &mut Window,
+        cx: &mut Context<RustDump>,
+    ) {
+        match page {
+            InfoDisplayPage::Resource(idx) => self.resources.select_leaf(*idx, window, cx),
+            InfoDisplayPage::Section(name) => self.build_section_view(name, window, cx),
+            InfoDisplayPage::Imports
+            | InfoDisplayPage::Import(_)
+            | InfoDisplayPage::DelayImport(_) => self.imports.open(page, window, cx),
+            InfoDisplayPage::Clr
+            | InfoDisplayPage::ClrTable(_)
+            | InfoDisplayPage::ClrUserStrings => self.clr.open(page, window, cx),
+            _ => self.build_view(page, window, cx),
+        }
+    }
+
+    fn scroll_offset(&self, page: &InfoDisplayPage, cx: &App) -> f32 {
+        self.scroll_table(page)
+            .map_or(0., |table| table.scroll_offset(cx))
+    }
+
+    fn set_scroll_offset(&mut self, offset: f32, page: &InfoDisplayPage, cx: &mut App) {
+        if let Some(table) = self.scroll_table(page) {
+            table.set_scroll_offset(offset, cx);
+        }
+    }
+
+    fn load(
+        &mut self,
+        cx: &mut Context<RustDump>,
+        window: &mut Window,
+        file: &mut LoadedFile,
+    ) -> anyhow::Result<()> {
+        self.load_file(file, cx, window)?;
         if let Some(pe_header) = &self.pe_header {
             self.dos_stub_hexview
                 .load_data(pe_header.dos_stub.clone(), window, cx, 64);
             self.dos_ascii_view = AsciiView::new(pe_header.dos_stub.clone(), cx);
         }
+        Ok(())
     }
 }
 
-fn parse_data(
+/// Field offset within its header plus the header's file offset, in the same
+/// little endian byte order as the hex views. Offsets past 0xFFFF use more bytes.
+fn header_offset_string(field_offset: Option<u16>, header_offset: usize) -> String {
+    let offset = field_offset.map_or(0, |field| field as usize + header_offset);
+    let mut offset_string = String::new();
+    let offset_bytes = if let Ok(offset) = u16::try_from(offset) {
+        offset.to_le_bytes().to_vec()
+    } else if let Ok(offset) = u32::try_from(offset) {
+        offset.to_le_bytes().to_vec()
+    } else {
+        (offset as u64).to_le_bytes().to_vec()
+    };
+    offset_bytes
+        .iter()
+        .for_each(|b| push_hex(&mut offset_string, *b));
+    offset_string
+}
+
+pub(crate) fn parse_data(
     data_value: Value,
     //data_chunk: Map<String, Value>,
     offset_fn: impl Fn(usize) -> String,
@@ -448,12 +1118,48 @@ fn parse_data(
                 name: k.clone(),
                 value: v.clone(),
                 meaning: meanings[i].to_string(),
+                tooltip: None,
+            };
+        })
+        .collect()
+}
+
+/// Replaces the meaning of enum and bitfield header fields with decoded names
+pub(crate) fn decode_fields(data: Vec<HeaderData>) -> Vec<HeaderData> {
+    data.into_iter()
+        .map(|row| {
+            let Some(value) = row.value.as_u64() else {
+                return row;
+            };
+            let (meaning, tooltip) = match row.name.as_str() {
+                "machine" => (
+                    flags::machine_name(value as u16).to_string(),
+                    row.meaning.clone(),
+                ),
+                "magic" => (
+                    flags::magic_name(value as u16).to_string(),
+                    row.meaning.clone(),
+                ),
+                "subsystem" => (
+                    flags::subsystem_name(value as u16).to_string(),
+                    row.meaning.clone(),
+                ),
+                "characteristics" => (
+                    flags::flag_names(value as u32, flags::FILE_FLAGS),
+                    flags::flag_tooltip(value as u32, flags::FILE_FLAGS),
+                ),
+                "dll_characteristics" => (
+                    flags::flag_names(value as u32, flags::DLL_FLAGS),
+                    flags::flag_tooltip(value as u32, flags::DLL_FLAGS),
+                ),
+                _ => return row,
             };
+            HeaderData { meaning, ..row }.with_tooltip(tooltip)
         })
         .collect()
 }
 
-fn parse_data_directory_from_array(
+pub(crate) fn parse_data_directory_from_array(
     data_directory: &[ImageDataDirectory; 16],
     offset_fn: impl Fn(usize) -> String,
 ) -> Vec<HeaderData> {
@@ -461,7 +1167,7 @@ fn parse_data_directory_from_array(
         .iter()
         .enumerate()
         .map(|(i, dir)| {
-            let dir_name = DIR_NAMES.get(i).copied().unwrap_or("Unknown Directory");
+            let dir_name = pe::DIR_NAMES.get(i).copied().unwrap_or("Unknown Directory");
 
             HeaderData {
                 offset: offset_fn(i),
@@ -475,6 +1181,7 @@ fn parse_data_directory_from_array(
                 } else {
                     "Present".to_string()
                 },
+                tooltip: None,
             }
         })
         .collect()
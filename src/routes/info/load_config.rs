@@ -0,0 +1,136 @@
+use gpui::{AnyElement, Context, IntoElement, ParentElement, Styled, Window, div};
+use gpui_component::table::Column;
+
+use crate::{
+    RustDump,
+    components::{
+        datatable::DataTable,
+        headertable::{HeaderData, HeaderTable},
+    },
+    pe::load_config::{self, LoadConfig},
+};
+
+pub struct LoadConfigView {
+    pub config: Option<LoadConfig>,
+    fields_table: HeaderTable,
+    tables_table: DataTable,
+}
+
+fn tables_columns() -> Vec<Column> {
+    vec![
+        Column::new("table", "Table")
+            .sortable()
+            .resizable(true)
+            .width(150.),
+        Column::new("rva", "RVA")
+            .sortable()
+            .resizable(true)
+            .width(100.),
+        Column::new("flags", "Flags").resizable(true).width(75.),
+    ]
+}
+
+impl LoadConfigView {
+    pub fn new(window: &mut Window, cx: &mut Context<RustDump>) -> Self {
+        Self {
+            config: None,
+            fields_table: HeaderTable::new(window, cx),
+            tables_table: DataTable::new(tables_columns(), window, cx),
+        }
+    }
+
+    pub fn load(
+        &mut self,
+        config: Option<LoadConfig>,
+        window: &mut Window,
+        cx: &mut Context<RustDump>,
+    ) {
+        let Some(cfg) = &config else {
+            self.fields_table.load(vec![], window, cx);
+            self.tables_table.load(vec![], window, cx);
+            self.config = None;
+            return;
+        };
+
+        let data = cfg
+            .fields
+            .iter()
+            .map(|field| {
+                let meaning = match field.name {
+                    "GuardFlags" => {
+                        let mut names: Vec<String> =
+                            load_config::guard_flag_names(field.value as u32)
+                                .iter()
+                                .map(|name| name.to_string())
+                                .collect();
+                        // Extra bytes per CFG table entry live in the top nibble
+                        if field.value >> 28 != 0 {
+                            names.push(format!("FUNCTION_TABLE_SIZE={}", field.value >> 28));
+                        }
+                        names.join(" | ")
+                    }
+                    "CHPEMetadataPointer" => match cfg.chpe_version {
+                        Some(version) => format!("{} (version {})", field.meaning, version),
+                        None => field.meaning.to_string(),
+                    },
+                    _ => field.meaning.to_string(),
+                };
+                HeaderData::new(
+                    field.offset,
+                    field.name,
+                    format!("0x{:0width$X}", field.value, width = field.size * 2),
+                    &meaning,
+                )
+            })
+            .collect();
+        self.fields_table.load(data, window, cx);
+
+        let se_handlers = cfg.se_handlers.iter().map(|rva| ("SafeSEH", *rva, None));
+        let cf_functions = cfg
+            .cf_functions
+            .iter()
+            .map(|(rva, flags)| ("CFG function", *rva, Some(*flags)));
+        let eh_continuations = cfg
+            .eh_continuations
+            .iter()
+            .map(|rva| ("EH continuation", *rva, None));
+        let rows = se_handlers
+            .chain(cf_functions)
+            .chain(eh_continuations)
+            .map(|(table, rva, flags)| {
+                vec![
+                    table.to_string(),
+                    format!("0x{:08X}", rva),
+                    flags
+                        .map(|flags| format!("0x{:02X}", flags))
+                        .unwrap_or_default(),
+                ]
+            })
+            .collect();
+        self.tables_table.load(rows, window, cx);
+
+        self.config = config;
+    }
+
+    pub fn scroll_table(&self) -> &HeaderTable {
+        &self.fields_table
+    }
+
+    pub fn render(&self) -> AnyElement {
+        if self.config.is_none() {
+            return div()
+                .p_2()
+                .child("No load configuration directory")
+                .into_any_element();
+        }
+
+        div()
+            .grid()
+            .grid_cols(2)
+            .grid_rows(1)
+            .size_full()
+            .child(self.fields_table.render())
+            .child(self.tables_table.render())
+            .into_any_element()
+    }
+}
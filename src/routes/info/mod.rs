@@ -1,2 +1,15 @@
+pub mod bound_imports;
+pub mod clr;
+pub mod debug;
+pub mod exceptions;
+pub mod exports;
+pub mod imports;
 pub mod info;
+pub mod load_config;
+pub mod relocations;
+pub mod resources;
+pub mod rich;
+pub mod section;
 pub mod section_hdrs_table;
+pub mod security;
+pub mod tls;
@@ -0,0 +1,156 @@
+use std::rc::Rc;
+
+use gpui::{AnyElement, Context, IntoElement, ParentElement, Styled, Window, div};
+use gpui_component::table::Column;
+
+use crate::{
+    RustDump,
+    components::datatable::DataTable,
+    pe::{
+        address::AddressSpace,
+        relocations::{self, RelocationBlock},
+    },
+};
+
+pub struct RelocationsView {
+    pub blocks: Vec<RelocationBlock>,
+    block_table: DataTable,
+    /// Entries of the selected block, refilled on block selection
+    entry_table: DataTable,
+}
+
+fn block_columns() -> Vec<Column> {
+    vec![
+        Column::new("offset", "Offset")
+            .sortable()
+            .resizable(true)
+            .width(100.),
+        Column::new("page", "Page RVA")
+            .sortable()
+            .resizable(true)
+            .width(100.),
+        Column::new("size", "Block Size")
+            .sortable()
+            .resizable(true)
+            .width(100.),
+        Column::new("count", "Entries")
+            .sortable()
+            .resizable(true)
+            .width(75.),
+    ]
+}
+
+fn entry_columns() -> Vec<Column> {
+    vec![
+        Column::new("raw", "Raw").resizable(true).width(75.),
+        Column::new("type", "Type")
+            .sortable()
+            .resizable(true)
+            .width(150.),
+        Column::new("offset", "Offset")
+            .sortable()
+            .resizable(true)
+            .width(75.),
+        Column::new("rva", "RVA")
+            .sortable()
+            .resizable(true)
+            .width(100.),
+        Column::new("va", "VA")
+            .sortable()
+            .resizable(true)
+            .width(175.),
+    ]
+}
+
+impl RelocationsView {
+    pub fn new(window: &mut Window, cx: &mut Context<RustDump>) -> Self {
+        Self {
+            blocks: vec![],
+            block_table: DataTable::new(block_columns(), window, cx),
+            entry_table: DataTable::new(entry_columns(), window, cx),
+        }
+    }
+
+    pub fn load(
+        &mut self,
+        blocks: Vec<RelocationBlock>,
+        machine: u16,
+        space: &AddressSpace,
+        window: &mut Window,
+        cx: &mut Context<RustDump>,
+    ) {
+        let rows = blocks
+            .iter()
+            .map(|block| {
+                vec![
+                    format!("0x{:08X}", block.offset),
+                    format!("0x{:08X}", block.page_rva),
+                    format!("0x{:X}", block.block_size),
+                    block.entries.len().to_string(),
+                ]
+            })
+            .collect();
+        self.block_table.load(rows, window, cx);
+
+        let entry_rows: Rc<Vec<Vec<Vec<String>>>> = Rc::new(
+            blocks
+                .iter()
+                .map(|block| {
+                    block
+                        .entries
+                        .iter()
+                        .map(|entry| {
+                            let kind = match entry.param {
+                                Some(param) => format!(
+                                    "{} (0x{:04X})",
+                                    relocations::type_name(entry.kind, machine),
+                                    param
+                                ),
+                                None => relocations::type_name(entry.kind, machine).to_string(),
+                            };
+                            vec![
+                                format!("0x{:04X}", entry.raw),
+                                kind,
+                                format!("0x{:03X}", entry.offset),
+                                format!("0x{:08X}", entry.rva),
+                                format!("0x{:X}", space.rva_to_va(entry.rva)),
+                            ]
+                        })
+                        .collect()
+                })
+                .collect(),
+        );
+        self.entry_table
+            .load(entry_rows.first().cloned().unwrap_or_default(), window, cx);
+
+        let entry_table = self.entry_table.clone();
+        self.block_table.on_row_select(cx, move |_app, row, cx| {
+            entry_table.set_rows(entry_rows.get(row).cloned().unwrap_or_default(), cx);
+            cx.notify();
+        });
+
+        self.blocks = blocks;
+    }
+
+    pub fn scroll_table(&self) -> &DataTable {
+        &self.block_table
+    }
+
+    pub fn render(&self) -> AnyElement {
+        if self.blocks.is_empty() {
+            return div()
+                .p_2()
+                .child("No base relocation directory")
+                .into_any_element();
+        }
+
+        div()
+            .grid()
+            .grid_cols(2)
+            .grid_rows(1)
+            .size_full()
+            .child(self.block_table.render())
+            .child(div().size_full().child(self.entry_table.render()))
+            .into_any_element()
+    }
+}
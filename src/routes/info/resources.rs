@@ -0,0 +1,155 @@
+use std::sync::Arc;
+
+use gpui::{
+    AnyElement, Context, Image, ImageFormat, IntoElement, ParentElement, SharedString, Styled,
+    Window, div, img,
+};
+use gpui_component::{StyledExt, table::Column};
+
+use crate::{
+    RustDump,
+    components::{asciiview::AsciiView, datatable::DataTable, hexview::Hexview},
+    pe::resources::{
+        self, ImagePreviewFormat, ResourceDirectory, ResourceLeaf, ResourcePreview, ResourceType,
+    },
+};
+
+enum LeafPreview {
+    Hex(Hexview, AsciiView),
+    Text(SharedString),
+    Table(DataTable),
+    Image(Arc<Image>),
+    Missing,
+}
+
+pub struct ResourcesView {
+    pub types: Vec<ResourceType>,
+    /// Set when the directory had more leaves than are listed
+    pub truncated: bool,
+    /// Every leaf in directory order, its data is read from `bytes` on selection
+    leaves: Vec<ResourceLeaf>,
+    bytes: Arc<Vec<u8>>,
+    /// Preview of the selected leaf, built on selection since hex views and tables are costly
+    preview: Option<(usize, LeafPreview)>,
+}
+
+impl ResourcesView {
+    pub fn new() -> Self {
+        Self {
+            types: vec![],
+            truncated: false,
+            leaves: vec![],
+            bytes: Arc::new(vec![]),
+            preview: None,
+        }
+    }
+
+    /// `bytes` is the whole file, which the leaf offsets point into
+    pub fn load(&mut self, resources: ResourceDirectory, bytes: Arc<Vec<u8>>) {
+        self.leaves = resources
+            .types
+            .iter()
+            .flat_map(|ty| ty.leaves.iter().cloned())
+            .collect();
+        self.types = resources.types;
+        self.truncated = resources.truncated;
+        self.bytes = bytes;
+        self.preview = None;
+    }
+
+    /// Decodes the leaf at `idx` for `render_leaf`, replacing the previous preview
+    pub fn select_leaf(&mut self, idx: usize, window: &mut Window, cx: &mut Context<RustDump>) {
+        if self
+            .preview
+            .as_ref()
+            .is_some_and(|(current, _)| *current == idx)
+        {
+            return;
+        }
+        let Some(leaf) = self.leaves.get(idx) else {
+            return;
+        };
+        let Some(data) = leaf.data(&self.bytes) else {
+            self.preview = Some((idx, LeafPreview::Missing));
+            return;
+        };
+
+        let preview = match resources::decode_preview(leaf, data) {
+            ResourcePreview::Hex => {
+                let mut hexview = Hexview::new(window, cx);
+                hexview.load_data(data.to_vec(), window, cx, 0);
+                LeafPreview::Hex(hexview, AsciiView::new(data.to_vec(), cx))
+            }
+            ResourcePreview::Text(text) => LeafPreview::Text(text.into()),
+            ResourcePreview::Table(rows) => {
+                let cols = vec![
+                    Column::new("key", "Key").resizable(true).width(250.),
+                    Column::new("value", "Value").resizable(true).width(500.),
+                ];
+                let mut table = DataTable::new(cols, window, cx);
+                let rows = rows.into_iter().map(|(k, v)| vec![k, v]).collect();
+                table.load(rows, window, cx);
+                LeafPreview::Table(table)
+            }
+            ResourcePreview::Image(format, bytes) => {
+                let format = match format {
+                    ImagePreviewFormat::Png => ImageFormat::Png,
+                    ImagePreviewFormat::Bmp => ImageFormat::Bmp,
+                };
+                LeafPreview::Image(Arc::new(Image::from_bytes(format, bytes)))
+            }
+        };
+        self.preview = Some((idx, preview));
+    }
+
+    /// Index of the first leaf belonging to the type at `type_idx` in the flat leaf list
+    pub fn first_leaf(&self, type_idx: usize) -> usize {
+        self.types[..type_idx]
+            .iter()
+            .map(|ty| ty.leaves.len())
+            .sum()
+    }
+
+    pub fn render_leaf(&self, idx: usize, cx: &Context<RustDump>) -> AnyElement {
+        let Some(leaf) = self.leaves.get(idx) else {
+            return div().into_any_element();
+        };
+        let preview = self
+            .preview
+            .as_ref()
+            .filter(|(current, _)| *current == idx)
+            .map(|(_, preview)| preview);
+
+        let title = format!(
+            "{} / {} / lang 0x{:04X} - RVA 0x{:08X}, {} bytes, code page {}",
+            resources::type_name(&leaf.type_id),
+            leaf.name,
+            leaf.language,
+            leaf.rva,
+            leaf.size,
+            leaf.code_page
+        );
+
+        let body = match preview {
+            None => div(),
+            Some(LeafPreview::Hex(hexview, ascii)) => div()
+                .grid()
+                .grid_cols(4)
+                .grid_rows(1)
+                .child(div().child(hexview.render()).col_span(3))
+                .child(div().child(ascii.render(cx)).mt(gpui::rems(2.)))
+                .size_full(),
+            Some(LeafPreview::Text(text)) => div().p_2().child(text.clone()),
+            Some(LeafPreview::Table(table)) => div().size_full().child(table.render()),
+            Some(LeafPreview::Image(image)) => div().p_2().child(img(image.clone())),
+            Some(LeafPreview::Missing) => div().p_2().child("Resource data lies outside the file"),
+        };
+
+        div()
+            .v_flex()
+            .size_full()
+            .child(div().p_2().child(title))
+            .child(body)
+            .into_any_element()
+    }
+}
@@ -0,0 +1,135 @@
+use gpui::{AnyElement, Context, IntoElement, ParentElement, Styled, Window, div};
+use gpui_component::table::Column;
+
+use crate::{
+    RustDump,
+    components::{
+        datatable::DataTable,
+        headertable::{HeaderData, HeaderTable},
+    },
+    pe::rich::RichHeader,
+};
+
+pub struct RichView {
+    pub rich: Option<RichHeader>,
+    summary_table: HeaderTable,
+    entries_table: DataTable,
+}
+
+fn entries_columns() -> Vec<Column> {
+    vec![
+        Column::new("offset", "Offset")
+            .sortable()
+            .resizable(true)
+            .width(100.),
+        Column::new("comp_id", "comp.id")
+            .sortable()
+            .resizable(true)
+            .width(100.),
+        Column::new("product_id", "Product ID")
+            .sortable()
+            .resizable(true)
+            .width(100.),
+        Column::new("product", "Product")
+            .sortable()
+            .resizable(true)
+            .width(175.),
+        Column::new("build", "Build")
+            .sortable()
+            .resizable(true)
+            .width(75.),
+        Column::new("vs", "Visual Studio")
+            .sortable()
+            .resizable(true)
+            .width(125.),
+        Column::new("count", "Count")
+            .sortable()
+            .resizable(true)
+            .width(75.),
+    ]
+}
+
+impl RichView {
+    pub fn new(window: &mut Window, cx: &mut Context<RustDump>) -> Self {
+        Self {
+            rich: None,
+            summary_table: HeaderTable::new(window, cx),
+            entries_table: DataTable::new(entries_columns(), window, cx),
+        }
+    }
+
+    pub fn load(
+        &mut self,
+        rich: Option<RichHeader>,
+        window: &mut Window,
+        cx: &mut Context<RustDump>,
+    ) {
+        let Some(hdr) = &rich else {
+            self.summary_table.load(vec![], window, cx);
+            self.entries_table.load(vec![], window, cx);
+            self.rich = None;
+            return;
+        };
+
+        let checksum_meaning = if hdr.checksum_valid() {
+            "Matches XOR key"
+        } else {
+            "MISMATCH: header was modified"
+        };
+        let data = vec![
+            HeaderData::new(hdr.offset, "DanS", "DanS", "Start of Rich header"),
+            HeaderData::new(hdr.end, "Rich", "Rich", "End of Rich header"),
+            HeaderData::new(
+                hdr.end + 4,
+                "Key",
+                format!("0x{:08X}", hdr.key),
+                "XOR key and stored checksum",
+            ),
+            HeaderData::new(
+                hdr.end + 4,
+                "Checksum",
+                format!("0x{:08X}", hdr.computed_checksum),
+                checksum_meaning,
+            ),
+        ];
+        self.summary_table.load(data, window, cx);
+
+        let rows = hdr
+            .entries
+            .iter()
+            .map(|entry| {
+                vec![
+                    format!("0x{:08X}", entry.offset),
+                    format!("0x{:08X}", entry.comp_id),
+                    format!("0x{:04X}", entry.product_id),
+                    entry.product_name(),
+                    entry.build.to_string(),
+                    entry.vs_version().to_string(),
+                    entry.count.to_string(),
+                ]
+            })
+            .collect();
+        self.entries_table.load(rows, window, cx);
+
+        self.rich = rich;
+    }
+
+    pub fn scroll_table(&self) -> &DataTable {
+        &self.entries_table
+    }
+
+    pub fn render(&self) -> AnyElement {
+        if self.rich.is_none() {
+            return div().p_2().child("No Rich header").into_any_element();
+        }
+
+        div()
+            .grid()
+            .grid_cols(2)
+            .grid_rows(1)
+            .size_full()
+            .child(self.summary_table.render())
+            .child(self.entries_table.render())
+            .into_any_element()
+    }
+}
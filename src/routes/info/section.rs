@@ -0,0 +1,177 @@
+use gpui::{AnyElement, Context, IntoElement, ParentElement, Styled, Window, div};
+use gpui_component::StyledExt;
+use pe_parse::SectionHeader;
+
+use crate::{
+    RustDump,
+    components::{
+        asciiview::AsciiView,
+        headertable::{HeaderData, HeaderTable},
+        hexview::Hexview,
+    },
+    pe::{address::AddressSpace, flags, sections},
+};
+
+/// The hex and ASCII views render every byte, so large sections are truncated
+const PREVIEW_LIMIT: usize = 0x10000;
+
+pub struct SectionView {
+    header_table: HeaderTable,
+    hexview: Hexview,
+    ascii_view: AsciiView,
+    raw_size: usize,
+    preview_size: usize,
+}
+
+impl SectionView {
+    pub fn new(
+        bytes: &[u8],
+        sct: &SectionHeader,
+        section_table_offset: usize,
+        space: &AddressSpace,
+        entropy: f64,
+        window: &mut Window,
+        cx: &mut Context<RustDump>,
+    ) -> Self {
+        let raw = sections::raw_data(bytes, sct);
+
+        // Memory past the raw data is zero filled, file bytes past the virtual size are padding
+        let padding = if sct.virtual_size > sct.size_of_raw_data {
+            format!(
+                "0x{:X} bytes zero filled in memory",
+                sct.virtual_size - sct.size_of_raw_data
+            )
+        } else if sct.size_of_raw_data > sct.virtual_size && sct.virtual_size != 0 {
+            format!(
+                "0x{:X} bytes of file alignment padding",
+                sct.size_of_raw_data - sct.virtual_size
+            )
+        } else {
+            "None".to_string()
+        };
+        let entropy_meaning = if entropy > 7.2 {
+            "High, likely compressed or encrypted"
+        } else {
+            "Bits per byte"
+        };
+
+        let off = section_table_offset;
+        let data = vec![
+            HeaderData::new(
+                off,
+                "Name",
+                String::from_utf8_lossy(&sct.name)
+                    .trim_end_matches('\0')
+                    .to_string(),
+                "Section name",
+            ),
+            HeaderData::new(
+                off + 8,
+                "VirtualSize",
+                format!("0x{:08X}", sct.virtual_size),
+                "Size in memory",
+            ),
+            HeaderData::new(
+                off + 12,
+                "VirtualAddress",
+                format!("0x{:08X}", sct.virtual_address),
+                &format!("RVA, VA 0x{:X}", space.rva_to_va(sct.virtual_address)),
+            ),
+            HeaderData::new(
+                off + 16,
+                "SizeOfRawData",
+                format!("0x{:08X}", sct.size_of_raw_data),
+                "Size on disk",
+            ),
+            HeaderData::new(
+                off + 20,
+                "PointerToRawData",
+                format!("0x{:08X}", sct.pointer_to_raw_data),
+                "File offset of raw data",
+            ),
+            HeaderData::new(
+                off + 24,
+                "PointerToRelocations",
+                format!("0x{:08X}", sct.pointer_to_relocations),
+                "COFF relocations, object files only",
+            ),
+            HeaderData::new(
+                off + 28,
+                "PointerToLinenumbers",
+                format!("0x{:08X}", sct.pointer_to_linenumbers),
+                "Deprecated COFF line numbers",
+            ),
+            HeaderData::new(
+                off + 32,
+                "NumberOfRelocations",
+                format!("0x{:04X}", sct.number_of_relocations),
+                "COFF relocation count",
+            ),
+            HeaderData::new(
+                off + 34,
+                "NumberOfLinenumbers",
+                format!("0x{:04X}", sct.number_of_linenumbers),
+                "COFF line number count",
+            ),
+            HeaderData::new(
+                off + 36,
+                "Characteristics",
+                format!("0x{:08X}", sct.characteristics),
+                &flags::section_flag_names(sct.characteristics),
+            )
+            .with_tooltip(flags::section_flag_tooltip(sct.characteristics)),
+            HeaderData::computed("Padding", padding, "Virtual vs raw size"),
+            HeaderData::computed("Entropy", format!("{:.4}", entropy), entropy_meaning),
+        ];
+        let mut header_table = HeaderTable::new(window, cx);
+        header_table.load(data, window, cx);
+
+        let preview = &raw[..raw.len().min(PREVIEW_LIMIT)];
+        let mut hexview = Hexview::new(window, cx);
+        hexview.load_data(
+            preview.to_vec(),
+            window,
+            cx,
+            sct.pointer_to_raw_data as usize,
+        );
+
+        Self {
+            header_table,
+            hexview,
+            ascii_view: AsciiView::new(preview.to_vec(), cx),
+            raw_size: raw.len(),
+            preview_size: preview.len(),
+        }
+    }
+
+    pub fn scroll_table(&self) -> &HeaderTable {
+        &self.header_table
+    }
+
+    pub fn render(&self, cx: &Context<RustDump>) -> AnyElement {
+        let data_view = if self.raw_size == 0 {
+            div().p_2().child("Section has no raw data")
+        } else {
+            div()
+                .grid()
+                .grid_cols(4)
+                .grid_rows(1)
+                .size_full()
+                .child(div().child(self.hexview.render()).col_span(3))
+                .child(div().child(self.ascii_view.render(cx)).mt(gpui::rems(2.)))
+        };
+
+        div()
+            .v_flex()
+            .size_full()
+            .child(div().h_1_3().child(self.header_table.render()))
+            .children((self.preview_size < self.raw_size).then(|| {
+                div().p_2().child(format!(
+                    "Showing the first 0x{:X} of 0x{:X} bytes",
+                    self.preview_size, self.raw_size
+                ))
+            }))
+            .child(div().flex_1().child(data_view))
+            .into_any_element()
+    }
+}
@@ -1,13 +1,21 @@
-use gpui::{App, AppContext, Context, Entity, IntoElement, Window};
+use gpui::{
+    App, AppContext, Context, Entity, InteractiveElement, IntoElement, ParentElement, SharedString,
+    StatefulInteractiveElement, Window, div,
+};
 use gpui_component::{
     Sizable,
     table::{Column, Table, TableDelegate, TableState},
+    tooltip::Tooltip,
 };
 use pe_parse::SectionHeader;
 use rd_core::hex_string;
 use serde_json::Value;
 
-use crate::RustDump;
+use crate::{
+    RustDump,
+    components::datatable::{ScrollTable, set_table_scroll_offset, table_scroll_offset},
+    pe::{address::AddressSpace, flags},
+};
 
 pub struct SectionsTable {
     pub table_state: Entity<TableState<SectionsTableDelegate>>,
@@ -25,15 +33,17 @@ impl SectionsTable {
         data: &Vec<SectionHeader>,
         window: &mut Window,
         cx: &mut Context<RustDump>,
-        image_base: u64,
+        space: &AddressSpace,
     ) {
         let data = data
             .iter()
             .map(|sct| SectionData {
                 name: String::from_utf8_lossy(&sct.name).to_string(),
-                addr: hex_string(&(sct.virtual_address as u64 + image_base).to_le_bytes()),
+                addr: hex_string(&space.rva_to_va(sct.virtual_address).to_le_bytes()),
                 raw_size: sct.size_of_raw_data.to_string(),
-                characteristics: hex_string(&sct.characteristics.to_le_bytes()),
+                characteristics: format!("0x{:08X}", sct.characteristics),
+                flags: flags::section_flag_names(sct.characteristics),
+                flags_tooltip: flags::section_flag_tooltip(sct.characteristics),
             })
             .collect();
 
@@ -46,12 +56,24 @@ impl SectionsTable {
     }
 }
 
+impl ScrollTable for SectionsTable {
+    fn scroll_offset(&self, cx: &App) -> f32 {
+        table_scroll_offset(&self.table_state, cx)
+    }
+
+    fn set_scroll_offset(&self, offset: f32, cx: &mut App) {
+        set_table_scroll_offset(&self.table_state, offset, cx);
+    }
+}
+
 #[derive(Debug)]
 pub struct SectionData {
     pub name: String,
     pub addr: String,
     pub raw_size: String,
     pub characteristics: String,
+    pub flags: String,
+    pub flags_tooltip: String,
 }
 
 #[derive(Debug)]
@@ -77,6 +99,9 @@ impl SectionsTableDelegate {
                 Column::new("chars", "Characteristics".to_string())
                     .resizable(true)
                     .width(175.),
+                Column::new("flags", "Flags".to_string())
+                    .resizable(true)
+                    .width(400.),
             ],
         }
     }
@@ -105,12 +130,25 @@ impl TableDelegate for SectionsTableDelegate {
         let row = &self.data[row_ix];
         let col = &self.cols[col_ix];
 
-        match col.key.as_ref() {
+        let text = match col.key.as_ref() {
             "name" => row.name.clone(),
             "addr" => row.addr.clone(),
             "raw_size" => row.raw_size.clone(),
             "chars" => row.characteristics.clone(),
+            "flags" => row.flags.clone(),
             _ => "".to_string(),
+        };
+
+        match col.key.as_ref() {
+            "chars" | "flags" if !row.flags_tooltip.is_empty() => {
+                let tooltip = SharedString::from(row.flags_tooltip.clone());
+                div()
+                    .id(("section_flags", row_ix * self.cols.len() + col_ix))
+                    .child(text)
+                    .tooltip(move |window, cx| Tooltip::new(tooltip.clone()).build(window, cx))
+                    .into_any_element()
+            }
+            _ => div().child(text).into_any_element(),
         }
     }
 }
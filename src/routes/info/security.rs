@@ -0,0 +1,160 @@
+use gpui::{AnyElement, Context, IntoElement, ParentElement, Styled, Window, div};
+use gpui_component::table::Column;
+
+use crate::{
+    RustDump,
+    components::datatable::DataTable,
+    pe::authenticode::{self, WinCertificate},
+};
+
+pub struct SecurityView {
+    pub certificates: Vec<WinCertificate>,
+    summary_table: DataTable,
+    chain_table: DataTable,
+}
+
+fn summary_columns() -> Vec<Column> {
+    vec![
+        Column::new("entry", "Entry").resizable(true).width(60.),
+        Column::new("field", "Field").resizable(true).width(150.),
+        Column::new("value", "Value").resizable(true).width(500.),
+    ]
+}
+
+fn chain_columns() -> Vec<Column> {
+    vec![
+        Column::new("signature", "Signature")
+            .sortable()
+            .resizable(true)
+            .width(80.),
+        Column::new("subject", "Subject")
+            .sortable()
+            .resizable(true)
+            .width(300.),
+        Column::new("issuer", "Issuer")
+            .sortable()
+            .resizable(true)
+            .width(300.),
+        Column::new("serial", "Serial").resizable(true).width(200.),
+        Column::new("not_before", "Not Before")
+            .sortable()
+            .resizable(true)
+            .width(175.),
+        Column::new("not_after", "Not After")
+            .sortable()
+            .resizable(true)
+            .width(175.),
+    ]
+}
+
+fn certificate_type_name(certificate_type: u16) -> &'static str {
+    match certificate_type {
+        0x0001 => "X509",
+        authenticode::WIN_CERT_TYPE_PKCS_SIGNED_DATA => "PKCS_SIGNED_DATA",
+        0x0003 => "RESERVED_1",
+        0x0004 => "TS_STACK_SIGNED",
+        _ => "Unknown",
+    }
+}
+
+impl SecurityView {
+    pub fn new(window: &mut Window, cx: &mut Context<RustDump>) -> Self {
+        Self {
+            certificates: vec![],
+            summary_table: DataTable::new(summary_columns(), window, cx),
+            chain_table: DataTable::new(chain_columns(), window, cx),
+        }
+    }
+
+    pub fn load(
+        &mut self,
+        certificates: Vec<WinCertificate>,
+        window: &mut Window,
+        cx: &mut Context<RustDump>,
+    ) {
+        let mut summary = vec![];
+        let mut chain = vec![];
+        for (i, cert) in certificates.iter().enumerate() {
+            let entry = i.to_string();
+            let mut push = |field: &str, value: String| {
+                summary.push(vec![entry.clone(), field.to_string(), value]);
+            };
+            push("Offset", format!("0x{:08X}", cert.offset));
+            push("Length", format!("0x{:X}", cert.length));
+            push("Revision", format!("0x{:04X}", cert.revision));
+            push(
+                "Type",
+                format!(
+                    "0x{:04X} ({})",
+                    cert.certificate_type,
+                    certificate_type_name(cert.certificate_type)
+                ),
+            );
+
+            for (j, sig) in cert.signatures.iter().enumerate() {
+                // Nested signatures are numbered after the primary one
+                let prefix = if cert.signatures.len() > 1 {
+                    format!("[{}] ", j)
+                } else {
+                    String::new()
+                };
+                let status = match sig.digest_matches() {
+                    Some(true) => "Digest matches",
+                    Some(false) => "Digest mismatch",
+                    None => "Not computed",
+                };
+                let fields = [
+                    ("Digest Algorithm", sig.digest_algorithm.clone()),
+                    ("Signed Digest", sig.signed_digest.clone()),
+                    (
+                        "Computed Digest",
+                        sig.computed_digest.clone().unwrap_or_default(),
+                    ),
+                    ("Digest Status", status.to_string()),
+                    ("Signer", sig.signer.clone().unwrap_or_default()),
+                    ("Signer Issuer", sig.signer_issuer.clone()),
+                    ("Signer Serial", sig.signer_serial.clone()),
+                    ("Signing Time", sig.signing_time.clone().unwrap_or_default()),
+                    ("Timestamp", sig.timestamp.clone().unwrap_or_default()),
+                ];
+                for (field, value) in fields {
+                    push(&format!("{}{}", prefix, field), value);
+                }
+
+                for info in &sig.certificates {
+                    chain.push(vec![
+                        format!("{}.{}", i, j),
+                        info.subject.clone(),
+                        info.issuer.clone(),
+                        info.serial.clone(),
+                        info.not_before.clone(),
+                        info.not_after.clone(),
+                    ]);
+                }
+            }
+        }
+        self.summary_table.load(summary, window, cx);
+        self.chain_table.load(chain, window, cx);
+
+        self.certificates = certificates;
+    }
+
+    pub fn scroll_table(&self) -> &DataTable {
+        &self.summary_table
+    }
+
+    pub fn render(&self) -> AnyElement {
+        if self.certificates.is_empty() {
+            return div().p_2().child("No certificate table").into_any_element();
+        }
+
+        div()
+            .grid()
+            .grid_cols(1)
+            .grid_rows(2)
+            .size_full()
+            .child(self.summary_table.render())
+            .child(self.chain_table.render())
+            .into_any_element()
+    }
+}
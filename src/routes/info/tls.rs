@@ -0,0 +1,149 @@
+use gpui::{AnyElement, Context, IntoElement, ParentElement, Styled, Window, div};
+use gpui_component::table::Column;
+
+use crate::{
+    RouteName, RustDump,
+    components::{
+        datatable::DataTable,
+        headertable::{HeaderData, HeaderTable},
+    },
+    pe::tls::TlsDirectory,
+};
+
+pub struct TlsView {
+    pub directory: Option<TlsDirectory>,
+    directory_table: HeaderTable,
+    callback_table: DataTable,
+}
+
+fn callback_columns() -> Vec<Column> {
+    vec![
+        Column::new("idx", "#").resizable(true).width(50.),
+        Column::new("va", "VA").resizable(true).width(175.),
+        Column::new("rva", "RVA").resizable(true).width(100.),
+        Column::new("offset", "File Offset")
+            .resizable(true)
+            .width(100.),
+    ]
+}
+
+impl TlsView {
+    pub fn new(window: &mut Window, cx: &mut Context<RustDump>) -> Self {
+        Self {
+            directory: None,
+            directory_table: HeaderTable::new(window, cx),
+            callback_table: DataTable::new(callback_columns(), window, cx),
+        }
+    }
+
+    pub fn load(
+        &mut self,
+        directory: Option<TlsDirectory>,
+        window: &mut Window,
+        cx: &mut Context<RustDump>,
+    ) {
+        let Some(dir) = &directory else {
+            self.directory_table.load(vec![], window, cx);
+            self.callback_table.load(vec![], window, cx);
+            self.directory = None;
+            return;
+        };
+
+        let off = dir.offset;
+        let width = if dir.is_64 { 8 } else { 4 };
+        let ptr = |va: u64| format!("0x{:X}", va);
+        let data = vec![
+            HeaderData::new(
+                off,
+                "StartAddressOfRawData",
+                ptr(dir.start_address_of_raw_data),
+                "Start VA of the TLS template",
+            ),
+            HeaderData::new(
+                off + width,
+                "EndAddressOfRawData",
+                ptr(dir.end_address_of_raw_data),
+                "End VA of the TLS template",
+            ),
+            HeaderData::new(
+                off + width * 2,
+                "AddressOfIndex",
+                ptr(dir.address_of_index),
+                "VA receiving the TLS index",
+            ),
+            HeaderData::new(
+                off + width * 3,
+                "AddressOfCallBacks",
+                ptr(dir.address_of_callbacks),
+                "VA of the callback array",
+            ),
+            HeaderData::new(
+                off + width * 4,
+                "SizeOfZeroFill",
+                dir.size_of_zero_fill,
+                "Zero bytes appended to the template",
+            ),
+            HeaderData::new(
+                off + width * 4 + 4,
+                "Characteristics",
+                format!("0x{:08X}", dir.characteristics),
+                "Alignment of the TLS data",
+            ),
+        ];
+        self.directory_table.load(data, window, cx);
+
+        let rows = dir
+            .callbacks
+            .iter()
+            .enumerate()
+            .map(|(i, callback)| {
+                vec![
+                    i.to_string(),
+                    format!("0x{:X}", callback.va),
+                    callback
+                        .rva
+                        .map(|rva| format!("0x{:08X}", rva))
+                        .unwrap_or_else(|| "-".to_string()),
+                    callback
+                        .file_offset
+                        .map(|offset| format!("0x{:08X}", offset))
+                        .unwrap_or_else(|| "unmapped".to_string()),
+                ]
+            })
+            .collect();
+        self.callback_table.load(rows, window, cx);
+
+        // Clicking a callback opens it in the disassembly
+        let callbacks: Vec<u64> = dir.callbacks.iter().map(|callback| callback.va).collect();
+        self.callback_table.on_row_select(cx, move |app, row, cx| {
+            let Some(va) = callbacks.get(row) else { return };
+            let tab = app.tab_mut();
+            if let Some(assembly) = tab.routes.get_mut(&RouteName::Assembly) {
+                assembly.go_to_address(*va);
+            }
+            tab.current_route = RouteName::Assembly;
+            cx.notify();
+        });
+
+        self.directory = directory;
+    }
+
+    pub fn scroll_table(&self) -> &DataTable {
+        &self.callback_table
+    }
+
+    pub fn render(&self) -> AnyElement {
+        if self.directory.is_none() {
+            return div().p_2().child("No TLS directory").into_any_element();
+        }
+
+        div()
+            .grid()
+            .grid_cols(2)
+            .grid_rows(1)
+            .size_full()
+            .child(self.directory_table.render())
+            .child(self.callback_table.render())
+            .into_any_element()
+    }
+}
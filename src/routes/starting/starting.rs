@@ -1,16 +1,25 @@
 use gpui::{AnyElement, Context, SharedString, Window, div, prelude::*};
-use gpui_component::ActiveTheme;
+use gpui_component::{
+    ActiveTheme,
+    button::{Button, ButtonVariants},
+};
 
-use crate::{Route, RustDump};
+use crate::{Route, RustDump, loader::LoadedFile};
 
 pub struct Starting {}
 
 impl Route for Starting {
-    fn render(&self, cx: &mut Context<RustDump>, _app: &RustDump) -> AnyElement {
-        self.render_route(cx)
+    fn render(&self, cx: &mut Context<RustDump>, app: &RustDump) -> AnyElement {
+        self.render_route(cx, app)
     }
 
-    fn load(&mut self, _cx: &mut Context<RustDump>, _window: &mut Window, _path: &std::path::Path) {
+    fn load(
+        &mut self,
+        _cx: &mut Context<RustDump>,
+        _window: &mut Window,
+        _file: &mut LoadedFile,
+    ) -> anyhow::Result<()> {
+        Ok(())
     }
 }
 
@@ -19,7 +28,7 @@ impl Starting {
         Self {}
     }
 
-    pub fn render_route(&self, cx: &mut Context<RustDump>) -> AnyElement {
+    pub fn render_route(&self, cx: &mut Context<RustDump>, app: &RustDump) -> AnyElement {
         div()
             .size_full()
             .flex()
@@ -30,7 +39,21 @@ impl Starting {
             .bg(cx.theme().background)
             .text_color(cx.theme().foreground)
             .font_family(SharedString::from("Diodrum Cyrillic"))
-            .child("Open file with Ctrl + O")
+            .gap_2()
+            .child("Open file with Ctrl + O or drop it here")
+            .when(!app.recent_files.is_empty(), |this| {
+                this.child(div().mt_4().child("Recent files")).children(
+                    app.recent_files.iter().enumerate().map(|(ix, path)| {
+                        let path = path.clone();
+                        Button::new(("recent", ix))
+                            .child(path.display().to_string())
+                            .on_click(cx.listener(move |app, _event, window, cx| {
+                                app.load_path(path.clone(), window, cx);
+                            }))
+                            .custom(app.custom_button)
+                    }),
+                )
+            })
             .into_any_element()
     }
 }
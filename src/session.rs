@@ -0,0 +1,127 @@
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Context as _;
+use gpui::{App, Context, Window};
+use serde::{Deserialize, Serialize};
+
+use crate::{InfoDisplayPage, RouteName, RustDump, tab::FileTab};
+
+const MAX_RECENT_FILES: usize = 10;
+
+/// Saved to the config directory so the app reopens where it was left
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Session {
+    /// Most recent first
+    pub recent_files: Vec<PathBuf>,
+    pub tabs: Vec<TabSession>,
+    /// Path rather than index, since saved tabs that fail to reopen shift the indices
+    pub active_path: Option<PathBuf>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct TabSession {
+    pub path: PathBuf,
+    pub current_route: RouteName,
+    pub info_page: InfoDisplayPage,
+    pub expand_nt: bool,
+    pub expand_sct: bool,
+    pub expand_section: bool,
+    pub expand_imports: bool,
+    pub expand_clr: bool,
+    pub expand_resources: bool,
+    pub expanded_resource_types: HashSet<usize>,
+    pub scroll_offsets: HashMap<RouteName, f32>,
+}
+
+/// Saved tabs still waiting to be reopened, one background load at a time
+pub struct Restore {
+    pub queue: VecDeque<TabSession>,
+    pub current: Option<TabSession>,
+    pub active_path: Option<PathBuf>,
+    /// Opened after the saved tabs, e.g. a file given on the command line
+    pub then_open: Option<PathBuf>,
+}
+
+fn session_path() -> Option<PathBuf> {
+    let config_dir = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("APPDATA").map(PathBuf::from))
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+    Some(config_dir.join("rustdump").join("session.json"))
+}
+
+impl Session {
+    /// A missing or unreadable session file gives an empty session
+    pub fn load() -> Self {
+        session_path()
+            .and_then(|path| fs::read(path).ok())
+            .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) -> anyhow::Result<()> {
+        let Some(path) = session_path() else {
+            return Ok(());
+        };
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)
+                .with_context(|| format!("failed to create {}", dir.display()))?;
+        }
+        fs::write(&path, serde_json::to_vec_pretty(self)?)
+            .with_context(|| format!("failed to write {}", path.display()))
+    }
+}
+
+pub fn push_recent(recent_files: &mut Vec<PathBuf>, path: &Path) {
+    recent_files.retain(|recent| recent != path);
+    recent_files.insert(0, path.to_path_buf());
+    recent_files.truncate(MAX_RECENT_FILES);
+}
+
+impl FileTab {
+    pub fn session(&self, cx: &App) -> TabSession {
+        TabSession {
+            path: self.path.clone(),
+            current_route: self.current_route,
+            info_page: self.info_page.clone(),
+            expand_nt: self.expand_nt,
+            expand_sct: self.expand_sct,
+            expand_section: self.expand_section,
+            expand_imports: self.expand_imports,
+            expand_clr: self.expand_clr,
+            expand_resources: self.expand_resources,
+            expanded_resource_types: self.expanded_resource_types.clone(),
+            scroll_offsets: self
+                .routes
+                .iter()
+                .map(|(name, route)| (*name, route.scroll_offset(&self.info_page, cx)))
+                .collect(),
+        }
+    }
+
+    /// Applies saved view state, keeping the route chosen on load if the saved one failed
+    pub fn restore(&mut self, saved: TabSession, window: &mut Window, cx: &mut Context<RustDump>) {
+        if !self.route_errors.contains_key(&saved.current_route) {
+            self.current_route = saved.current_route;
+        }
+        self.set_info_page(saved.info_page, window, cx);
+        self.expand_nt = saved.expand_nt;
+        self.expand_sct = saved.expand_sct;
+        self.expand_section = saved.expand_section;
+        self.expand_imports = saved.expand_imports;
+        self.expand_clr = saved.expand_clr;
+        self.expand_resources = saved.expand_resources;
+        self.expanded_resource_types = saved.expanded_resource_types;
+        // After the Info page is set, the Info route scrolls that page's table
+        for (name, offset) in saved.scroll_offsets {
+            if let Some(route) = self.routes.get_mut(&name) {
+                route.set_scroll_offset(offset, &self.info_page, cx);
+            }
+        }
+    }
+}
@@ -0,0 +1,162 @@
+use std::{
+    collections::{HashMap, HashSet},
+    path::PathBuf,
+    sync::Arc,
+};
+
+use gpui::{
+    AnyElement, Context, InteractiveElement, IntoElement, ParentElement, SharedString, Styled,
+    Window, div, prelude::FluentBuilder,
+};
+use gpui_component::{
+    ActiveTheme, StyledExt,
+    button::{Button, ButtonVariants},
+};
+use pe_parse::PEHeader;
+
+use crate::{
+    InfoDisplayPage, Route, RouteName, RustDump,
+    pe::{address::AddressSpace, exports::ExportDirectory, imports::ImportedDll},
+    routes::{
+        assembly::assembly::Assembly,
+        hexdump::{hexdump::Hexdump, search::HexSearch},
+        info::info::Info,
+    },
+};
+
+/// One open file with its own routes and view state
+pub struct FileTab {
+    pub path: PathBuf,
+    pub current_route: RouteName,
+    pub routes: HashMap<RouteName, Box<dyn Route>>,
+    /// Routes that failed to load the file, shown as an error page
+    pub route_errors: HashMap<RouteName, SharedString>,
+    pub info_page: InfoDisplayPage,
+    pub expand_nt: bool,
+    pub expand_sct: bool,
+    pub expand_section: bool,
+    pub expand_imports: bool,
+    pub expand_clr: bool,
+    pub expand_resources: bool,
+    pub expanded_resource_types: HashSet<usize>,
+    /// `None` when the file is not a valid PE
+    pub pe_header: Option<PEHeader>,
+    /// Shared with the search, which runs on the background executor
+    pub bytes: Arc<Vec<u8>>,
+    /// Parsed imports and exports, kept for the exported report
+    pub imports: Vec<ImportedDll>,
+    pub exports: Option<ExportDirectory>,
+    pub search: HexSearch,
+}
+
+impl FileTab {
+    pub fn new(path: PathBuf, window: &mut Window, cx: &mut Context<RustDump>) -> Self {
+        let mut routes: HashMap<RouteName, Box<dyn Route>> = HashMap::new();
+        routes.insert(RouteName::Info, Box::new(Info::new(window, cx)));
+        routes.insert(RouteName::Hexdump, Box::new(Hexdump::new(window, cx)));
+        routes.insert(RouteName::Assembly, Box::new(Assembly::new(window, cx)));
+
+        Self {
+            path,
+            current_route: RouteName::Info,
+            routes,
+            route_errors: HashMap::new(),
+            info_page: InfoDisplayPage::DOSHeaders,
+            expand_nt: false,
+            expand_sct: false,
+            expand_section: false,
+            expand_imports: false,
+            expand_clr: false,
+            expand_resources: false,
+            expanded_resource_types: HashSet::new(),
+            pe_header: None,
+            bytes: Arc::new(vec![]),
+            imports: vec![],
+            exports: None,
+            search: HexSearch::new(window, cx),
+        }
+    }
+
+    /// Converts between file offsets, RVAs and VAs of the file
+    pub fn address_space(&self) -> Option<AddressSpace<'_>> {
+        self.pe_header
+            .as_ref()
+            .map(|pe_header| AddressSpace::new(pe_header).with_file_size(self.bytes.len()))
+    }
+
+    pub fn set_info_page(
+        &mut self,
+        page: InfoDisplayPage,
+        window: &mut Window,
+        cx: &mut Context<RustDump>,
+    ) {
+        if let Some(info) = self.routes.get_mut(&RouteName::Info) {
+            info.open_info_page(&page, window, cx);
+        }
+        self.info_page = page;
+    }
+
+    pub fn title(&self) -> String {
+        self.path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| self.path.display().to_string())
+    }
+
+    pub fn render(&self, cx: &mut Context<RustDump>, app: &RustDump) -> AnyElement {
+        let Some(err) = self.route_errors.get(&self.current_route) else {
+            return self.routes[&self.current_route].render(cx, app);
+        };
+
+        div()
+            .v_flex()
+            .size_full()
+            .justify_center()
+            .items_center()
+            .gap_2()
+            .bg(cx.theme().background)
+            .font_family(SharedString::from("Diodrum Cyrillic"))
+            .child("Failed to load file")
+            .child(div().text_color(cx.theme().danger).child(err.clone()))
+            .into_any_element()
+    }
+}
+
+impl RustDump {
+    pub fn render_tab_bar(&self, cx: &mut Context<RustDump>) -> impl IntoElement {
+        div()
+            .h_flex()
+            .border_b_1()
+            .border_color(cx.theme().sidebar_border)
+            .bg(cx.theme().background)
+            .font_family("Diodrum Cyrillic")
+            .children(self.tabs.iter().enumerate().map(|(ix, tab)| {
+                div()
+                    .id(("tab", ix))
+                    .h_flex()
+                    .border_r_1()
+                    .border_color(cx.theme().sidebar_border)
+                    .when(ix == self.active_tab, |this| {
+                        this.border_b_2().border_color(cx.theme().primary)
+                    })
+                    .child(
+                        Button::new(("tab_select", ix))
+                            .child(tab.title())
+                            .on_click(cx.listener(move |app, _event, _window, cx| {
+                                app.active_tab = ix;
+                                cx.notify();
+                            }))
+                            .custom(self.custom_button)
+                            .pl_4(),
+                    )
+                    .child(
+                        Button::new(("tab_close", ix))
+                            .child("×")
+                            .on_click(cx.listener(move |app, _event, _window, cx| {
+                                app.close_tab(ix, cx);
+                            }))
+                            .custom(self.custom_button),
+                    )
+            }))
+    }
+}